@@ -1,9 +1,9 @@
 use std::io::Error as IoError;
 #[cfg(any(feature = "std_io", feature = "futures_util_io"))]
-use std::io::{Cursor, ErrorKind as IoErrorKind};
+use std::io::ErrorKind as IoErrorKind;
 
 #[cfg(any(feature = "std_io", feature = "futures_util_io"))]
-use tls_client_hello_parser::ClientHelloPayload;
+use tls_client_hello_parser::{ClientHelloPayload, Progress};
 use tls_client_hello_parser::{ParseError, Parser};
 
 #[cfg(feature = "std_io")]
@@ -33,8 +33,10 @@ impl Detector {
 
     #[cfg(feature = "std_io")]
     pub fn detect<P: Peek>(&mut self, p: &mut P) -> Result<ClientHelloPayload, DetectError> {
+        self.parser = Parser::new();
+
         let mut v = vec![0u8; 16 * 1024];
-        let mut buf = Cursor::new(vec![]);
+        let mut fed = 0;
 
         loop {
             match p.peek_sync(&mut v).map_err(DetectError::IoError)? {
@@ -45,14 +47,16 @@ impl Detector {
                     )))
                 }
                 n => {
-                    buf.get_mut().extend_from_slice(&v[..n]);
-                    match self
+                    // Peeking returns the stream from its start, only feed the new bytes.
+                    let progress = self
                         .parser
-                        .parse(&mut buf)
-                        .map_err(DetectError::ParseError)?
-                    {
-                        Some(chp) => return Ok(chp),
-                        None => continue,
+                        .feed(&v[fed.min(n)..n])
+                        .map_err(DetectError::ParseError)?;
+                    fed = fed.max(n);
+                    match progress {
+                        Progress::NeedMore { at_least } => grow(&mut v, fed + at_least),
+                        Progress::Done(chp, _) => return Ok(chp),
+                        Progress::NotTls => return Err(DetectError::NotTls),
                     }
                 }
             }
@@ -64,8 +68,10 @@ impl Detector {
         &mut self,
         p: &mut P,
     ) -> Result<ClientHelloPayload, DetectError> {
+        self.parser = Parser::new();
+
         let mut v = vec![0u8; 16 * 1024];
-        let mut buf = Cursor::new(vec![]);
+        let mut fed = 0;
 
         loop {
             match p.peek_async(&mut v).await.map_err(DetectError::IoError)? {
@@ -76,14 +82,16 @@ impl Detector {
                     )))
                 }
                 n => {
-                    buf.get_mut().extend_from_slice(&v[..n]);
-                    match self
+                    // Peeking returns the stream from its start, only feed the new bytes.
+                    let progress = self
                         .parser
-                        .parse(&mut buf)
-                        .map_err(DetectError::ParseError)?
-                    {
-                        Some(chp) => return Ok(chp),
-                        None => continue,
+                        .feed(&v[fed.min(n)..n])
+                        .map_err(DetectError::ParseError)?;
+                    fed = fed.max(n);
+                    match progress {
+                        Progress::NeedMore { at_least } => grow(&mut v, fed + at_least),
+                        Progress::Done(chp, _) => return Ok(chp),
+                        Progress::NotTls => return Err(DetectError::NotTls),
                     }
                 }
             }
@@ -91,11 +99,20 @@ impl Detector {
     }
 }
 
+#[cfg(any(feature = "std_io", feature = "futures_util_io"))]
+fn grow(v: &mut Vec<u8>, len: usize) {
+    if v.len() < len {
+        v.resize(len, 0);
+    }
+}
+
 //
 #[derive(Debug)]
 pub enum DetectError {
     IoError(IoError),
     ParseError(ParseError),
+    /// The stream doesn't start with a TLS ClientHello record, e.g. plaintext HTTP or SSH.
+    NotTls,
 }
 impl core::fmt::Display for DetectError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -13,7 +13,7 @@ use tls_mkcert_test::{
     SNI,
 };
 
-use stream_tls_client_hello_detector::Detector;
+use stream_tls_client_hello_detector::{DetectError, Detector};
 
 // ref https://github.com/bk-rs/rust-io-peek/blob/master/std-io-peek/tests/tcp_stream.rs
 // ref https://github.com/async-rs/async-tls/blob/master/examples/server/src/main.rs
@@ -57,13 +57,7 @@ fn async_io_async_tcp_stream() -> Result<(), Box<dyn std::error::Error>> {
                 .await
                 .expect("detector.detect_async");
             println!("{client_hello_payload:?}");
-            assert_eq!(
-                client_hello_payload
-                    .client_hello()
-                    .expect("client_hello_payload.client_hello")
-                    .server_name(),
-                Some(SNI)
-            );
+            assert_eq!(client_hello_payload.client_hello().server_name(), Some(SNI));
 
             let mut tls_stream_s = tls_acceptor
                 .accept(tcp_stream_s)
@@ -173,13 +167,7 @@ async fn tokio_tcp_stream() -> Result<(), Box<dyn std::error::Error>> {
             .await
             .expect("detector.detect_async");
         println!("{client_hello_payload:?}");
-        assert_eq!(
-            client_hello_payload
-                .client_hello()
-                .expect("client_hello_payload.client_hello")
-                .server_name(),
-            Some(SNI)
-        );
+        assert_eq!(client_hello_payload.client_hello().server_name(), Some(SNI));
 
         let tcp_stream_s = Compat::new(tcp_stream_s);
         let mut tls_stream_s = tls_acceptor
@@ -245,3 +233,30 @@ async fn tokio_tcp_stream() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn tokio_tcp_stream_not_tls() -> Result<(), Box<dyn std::error::Error>> {
+    use std::net::Ipv4Addr;
+
+    use tokio::{
+        io::AsyncWriteExt as _,
+        net::{TcpListener, TcpStream},
+    };
+
+    let listener = TcpListener::bind((Ipv4Addr::new(127, 0, 0, 1), 0)).await?;
+    let addr = listener.local_addr()?;
+
+    let mut tcp_stream_c = TcpStream::connect(addr).await?;
+    let (mut tcp_stream_s, _) = listener.accept().await?;
+
+    // Only the first byte, the detector must not wait for a whole record header.
+    tcp_stream_c.write_all(b"G").await?;
+
+    let mut detector = Detector::new();
+    match detector.detect_async(&mut tcp_stream_s).await {
+        Err(DetectError::NotTls) => {}
+        x => panic!("{x:?}"),
+    }
+
+    Ok(())
+}
@@ -47,13 +47,7 @@ fn tcp_stream() -> Result<(), Box<dyn std::error::Error>> {
             let mut detector = Detector::new();
             let client_hello_payload = detector.detect(&mut tcp_stream_s).expect("detector.detect");
             println!("{client_hello_payload:?}");
-            assert_eq!(
-                client_hello_payload
-                    .client_hello()
-                    .expect("client_hello_payload.client_hello")
-                    .server_name(),
-                Some(SNI)
-            );
+            assert_eq!(client_hello_payload.client_hello().server_name(), Some(SNI));
 
             let tcp_stream_s = Async::new(tcp_stream_s).expect("Async::new");
 
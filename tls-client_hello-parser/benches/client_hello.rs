@@ -0,0 +1,50 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use tls_client_hello_parser::{ClientHelloRef, Parser};
+use tls_mkcert_test::rustls::make_client_connection;
+
+fn client_hello_bytes() -> Vec<u8> {
+    let mut client = make_client_connection().expect("make_client_connection");
+    let mut buf = Vec::new();
+    client.write_tls(&mut buf).expect("client.write_tls");
+    buf
+}
+
+fn bench(c: &mut Criterion) {
+    let buf = client_hello_bytes();
+
+    let mut group = c.benchmark_group("client_hello");
+
+    group.bench_function("owned", |b| {
+        b.iter(|| {
+            let mut parser = Parser::new();
+            let chp = parser
+                .parse_bytes(black_box(&buf))
+                .expect("parser.parse_bytes")
+                .expect("parser.parse_bytes none");
+            black_box(chp.get_sni_extension().is_some())
+        })
+    });
+
+    group.bench_function("borrowed", |b| {
+        b.iter(|| {
+            let chr = ClientHelloRef::parse(black_box(&buf))
+                .expect("ClientHelloRef::parse")
+                .expect("ClientHelloRef::parse none");
+            black_box(chr.sni_host_name().expect("chr.sni_host_name").is_some())
+        })
+    });
+
+    group.bench_function("borrowed_to_owned", |b| {
+        b.iter(|| {
+            let chr = ClientHelloRef::parse(black_box(&buf))
+                .expect("ClientHelloRef::parse")
+                .expect("ClientHelloRef::parse none");
+            black_box(chr.to_owned().expect("chr.to_owned"))
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
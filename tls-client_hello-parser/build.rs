@@ -0,0 +1,142 @@
+// Generates the IANA registries of `src/iana.rs` from the CSV files in `data/`, as downloaded
+// from https://www.iana.org/protocols.
+
+use std::{env, fmt::Write as _, fs, path::Path};
+
+const REGISTRIES: &[(&str, &str)] = &[
+    ("CIPHER_SUITES", "data/tls-parameters-4.csv"),
+    ("SUPPORTED_GROUPS", "data/tls-parameters-8.csv"),
+    ("SIGNATURE_SCHEMES", "data/tls-signaturescheme.csv"),
+    ("EXTENSION_TYPES", "data/tls-extensiontype-values-1.csv"),
+];
+
+fn main() {
+    let mut out = String::new();
+
+    for (name, path) in REGISTRIES {
+        println!("cargo:rerun-if-changed={path}");
+
+        let csv = fs::read_to_string(path).unwrap_or_else(|err| panic!("{path}: {err}"));
+        let mut rows = parse_csv(&csv).into_iter();
+        let header = rows.next().unwrap_or_default();
+        let column = |names: &[&str]| {
+            header
+                .iter()
+                .position(|x| names.contains(&x.as_str()))
+                .unwrap_or_else(|| panic!("{path}: no {names:?} column"))
+        };
+        let (value, description, recommended, reference) = (
+            column(&["Value"]),
+            column(&["Description", "Extension Name", "TLS ExtensionType Name"]),
+            column(&["Recommended"]),
+            column(&["Reference"]),
+        );
+
+        let mut entries = Vec::new();
+        for row in rows {
+            let field = |i: usize| row.get(i).map(|x| x.trim()).unwrap_or_default();
+            if field(description) == "Unassigned" {
+                continue;
+            }
+            let (first, last) = parse_value(field(value))
+                .unwrap_or_else(|| panic!("{path}: invalid value {:?}", field(value)));
+            let recommended = match field(recommended) {
+                "Y" => "Yes",
+                "D" => "Discouraged",
+                _ => "No",
+            };
+            entries.push((
+                first,
+                last,
+                field(description).to_owned(),
+                field(reference).to_owned(),
+                recommended,
+            ));
+        }
+        entries.sort_by_key(|x| x.0);
+        for pair in entries.windows(2) {
+            assert!(pair[0].1 < pair[1].0, "{path}: overlapping {:?}", pair[1].2);
+        }
+
+        writeln!(out, "static {name}: &[(u16, u16, Entry)] = &[").unwrap();
+        for (first, last, description, reference, recommended) in entries {
+            writeln!(
+                out,
+                "    (0x{first:04x}, 0x{last:04x}, Entry {{ name: {description:?}, reference: {reference:?}, recommended: Recommended::{recommended} }}),"
+            )
+            .unwrap();
+        }
+        writeln!(out, "];").unwrap();
+    }
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR");
+    fs::write(Path::new(&out_dir).join("iana.rs"), out).expect("write iana.rs");
+}
+
+// `0x13,0x01`, `0x00,0x1C-1D` and `0xFF,0x00-FF` for cipher suites, `0x0401`, `29` or
+// `65024-65279` otherwise.
+fn parse_value(s: &str) -> Option<(u16, u16)> {
+    fn int(s: &str) -> Option<u16> {
+        let s = s.trim();
+        match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            Some(hex) => u16::from_str_radix(hex, 16).ok(),
+            None => s.parse().ok(),
+        }
+    }
+    fn range(s: &str, hex: bool) -> Option<(u16, u16)> {
+        let (first, last) = s.split_once('-').unwrap_or((s, s));
+        let last = last.trim();
+        let last = if hex && !last.starts_with("0x") {
+            u16::from_str_radix(last, 16).ok()?
+        } else {
+            int(last)?
+        };
+        Some((int(first)?, last))
+    }
+
+    match s.split_once(',') {
+        Some((hi, "*")) => {
+            let (first, last) = range(hi, true)?;
+            Some((first << 8, last << 8 | 0xff))
+        }
+        Some((hi, lo)) => {
+            let hi = int(hi)?;
+            let (first, last) = range(lo, true)?;
+            (first <= 0xff && last <= 0xff).then_some((hi << 8 | first, hi << 8 | last))
+        }
+        None => range(s, false),
+    }
+}
+
+// RFC 4180, quoted fields may contain commas, newlines and doubled quotes.
+fn parse_csv(s: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') => quoted = true,
+            (false, ',') => row.push(std::mem::take(&mut field)),
+            (false, '\r') => {}
+            (false, '\n') => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
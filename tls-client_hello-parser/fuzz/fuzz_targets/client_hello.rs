@@ -0,0 +1,39 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tls_client_hello_parser::{
+    codec::Codec as _, ClientHelloPayload, ClientHelloRef, Parser, Progress,
+};
+
+fuzz_target!(|data: &[u8]| {
+    // Whole input at once, then one byte at a time, both must agree.
+    let whole = Parser::new().feed(data);
+
+    let mut parser = Parser::new();
+    let mut fed = Ok(Progress::NeedMore { at_least: 1 });
+    for b in data {
+        fed = parser.feed(core::slice::from_ref(b));
+        if !matches!(fed, Ok(Progress::NeedMore { .. })) {
+            break;
+        }
+    }
+    match (&whole, &fed) {
+        (Ok(Progress::Done(a, _)), Ok(Progress::Done(b, _))) => assert_eq!(a, b),
+        (Ok(Progress::Done(..)), x) | (x, Ok(Progress::Done(..))) if !data.is_empty() => {
+            panic!("{whole:?} {x:?}")
+        }
+        _ => {}
+    }
+
+    let _ = Parser::new().parse_bytes(data);
+    let _ = ClientHelloRef::parse(data);
+
+    if let Ok(chr) = ClientHelloRef::read_bytes(data) {
+        let _ = chr.sni_host_name();
+        for ext in chr.extensions() {
+            let _ = ext.decode();
+        }
+        let _ = chr.to_owned();
+    }
+    let _ = ClientHelloPayload::read_bytes(data);
+});
@@ -0,0 +1,35 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tls_client_hello_parser::{codec::Codec as _, ja4::Transport, ClientHelloPayload};
+
+fuzz_target!(|data: &[u8]| {
+    let chp = match ClientHelloPayload::read_bytes(data) {
+        Ok(x) => x,
+        Err(_) => return,
+    };
+
+    let client_hello = chp.client_hello();
+    let _ = client_hello.server_name();
+    let _ = client_hello.server_names().map(|x| x.count());
+    let _ = client_hello.normalized_server_name();
+    let _ = client_hello.signature_schemes_without_grease().map(|x| x.count());
+    let _ = client_hello.alpn_without_grease().map(|x| x.count());
+    let _ = client_hello.extensions().count();
+    let _ = client_hello.duplicate_extensions();
+    let _ = client_hello.unknown_extensions().count();
+    let _ = client_hello.supported_versions();
+    let _ = client_hello.supported_groups();
+    let _ = client_hello.key_shares().map(|x| x.count());
+    let _ = client_hello.psk_modes();
+    let _ = client_hello.effective_max_version();
+    let _ = client_hello.cipher_suites_without_grease().count();
+    let _ = client_hello.validate_for_rustls();
+
+    let _ = chp.ja3_string();
+    let _ = chp.ja3_hash();
+    for transport in [Transport::Tcp, Transport::Quic, Transport::Dtls] {
+        let _ = chp.ja4(transport);
+        let _ = chp.ja4_r(transport);
+    }
+});
@@ -0,0 +1,22 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tls_client_hello_parser::{
+    message::read_client_hello_message,
+    record::{is_not_tls, read_record},
+};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = is_not_tls(data);
+
+    let mut rest = data;
+    while let Ok(Some((header, fragment))) = read_record(rest) {
+        rest = &rest[5 + usize::from(header.len)..];
+        assert_eq!(fragment.len(), usize::from(header.len));
+    }
+
+    if let Ok(Some(msg)) = read_client_hello_message(data) {
+        assert!(msg.len <= data.len());
+        let _ = msg.input_offset(msg.body.len());
+    }
+});
@@ -0,0 +1,26 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tls_client_hello_parser::{ClientHelloPayload, Parser, Progress};
+
+fuzz_target!(|input: (ClientHelloPayload, u16)| {
+    let (chp, fragment_len) = input;
+
+    let handshake = chp.to_handshake_bytes().unwrap();
+
+    let mut buf = Vec::new();
+    for fragment in handshake.chunks(usize::from(fragment_len).clamp(1, 16384)) {
+        buf.extend_from_slice(&[0x16, 0x03, 0x01]);
+        buf.extend_from_slice(&(fragment.len() as u16).to_be_bytes());
+        buf.extend_from_slice(fragment);
+    }
+
+    match Parser::new().feed(&buf) {
+        Ok(Progress::Done(parsed, len)) => {
+            assert_eq!(parsed, chp);
+            assert_eq!(len, buf.len());
+            assert_eq!(parsed.to_handshake_bytes().unwrap(), handshake);
+        }
+        x => panic!("{x:?}"),
+    }
+});
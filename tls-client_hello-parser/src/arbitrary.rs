@@ -0,0 +1,136 @@
+//! `Arbitrary` implementations generating structurally valid ClientHellos.
+//!
+//! Every generated value survives an encode/parse roundtrip: extension types are unique, their
+//! data decodes back to the same variant and all lengths fit their wire fields.
+
+use alloc::{vec, vec::Vec};
+use core::ops::RangeInclusive;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{
+    codec::{PayloadU16, PayloadU8},
+    enums::{
+        CipherSuite, Compression, ECPointFormat, ExtensionType, NamedGroup, PSKKeyExchangeMode,
+        ProtocolVersion, ServerNameType, SignatureScheme,
+    },
+    handshake::{
+        ClientExtension, ClientHelloPayload, KeyShareEntry, Random, ServerName, SessionId,
+        UnknownExtension,
+    },
+};
+
+fn vec_of<'a, T>(
+    u: &mut Unstructured<'a>,
+    len: RangeInclusive<usize>,
+    mut f: impl FnMut(&mut Unstructured<'a>) -> Result<T>,
+) -> Result<Vec<T>> {
+    let len = u.int_in_range(len)?;
+    (0..len).map(|_| f(u)).collect()
+}
+
+fn bytes(u: &mut Unstructured<'_>, len: RangeInclusive<usize>) -> Result<Vec<u8>> {
+    vec_of(u, len, |u| u.arbitrary())
+}
+
+fn host_name(u: &mut Unstructured<'_>) -> Result<Vec<u8>> {
+    const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+
+    let labels = vec_of(u, 1..=4, |u| {
+        vec_of(u, 1..=12, |u| u.choose(CHARS).copied())
+    })?;
+    let mut name = labels.join(&b'.');
+    // Keep the top-level label from being all-numeric.
+    name.push(b'x');
+    Ok(name)
+}
+
+// Types `ClientExtension::read_payload` decodes to their own variant.
+fn is_decoded(typ: ExtensionType) -> bool {
+    matches!(
+        typ,
+        ExtensionType::ECPointFormats
+            | ExtensionType::EllipticCurves
+            | ExtensionType::ServerName
+            | ExtensionType::SignatureAlgorithms
+            | ExtensionType::ALProtocolNegotiation
+            | ExtensionType::SupportedVersions
+            | ExtensionType::KeyShare
+            | ExtensionType::PSKKeyExchangeModes
+    )
+}
+
+impl<'a> Arbitrary<'a> for ClientExtension {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=8)? {
+            0 => Self::ECPointFormats(vec_of(u, 1..=4, |u| {
+                Ok(ECPointFormat::from(u8::arbitrary(u)?))
+            })?),
+            1 => Self::NamedGroups(vec_of(u, 1..=16, |u| {
+                Ok(NamedGroup::from(u16::arbitrary(u)?))
+            })?),
+            2 => Self::ServerName(vec_of(u, 1..=2, |u| {
+                Ok(ServerName {
+                    typ: ServerNameType::HostName,
+                    payload: PayloadU16(host_name(u)?),
+                })
+            })?),
+            3 => Self::SignatureAlgorithms(vec_of(u, 1..=16, |u| {
+                Ok(SignatureScheme::from(u16::arbitrary(u)?))
+            })?),
+            4 => Self::Protocols(vec_of(u, 1..=4, |u| Ok(PayloadU8(bytes(u, 1..=16)?)))?),
+            5 => Self::SupportedVersions(vec_of(u, 1..=4, |u| {
+                Ok(ProtocolVersion::from(u16::arbitrary(u)?))
+            })?),
+            6 => Self::KeyShare(vec_of(u, 0..=3, |u| {
+                Ok(KeyShareEntry {
+                    group: NamedGroup::from(u16::arbitrary(u)?),
+                    payload: PayloadU16(bytes(u, 1..=64)?),
+                })
+            })?),
+            7 => Self::PresharedKeyModes(vec_of(u, 1..=2, |u| {
+                Ok(PSKKeyExchangeMode::from(u8::arbitrary(u)?))
+            })?),
+            _ => {
+                let mut typ = ExtensionType::from(u16::arbitrary(u)?);
+                if is_decoded(typ) {
+                    typ = ExtensionType::from(u16::from(typ) | 0xff00);
+                }
+                Self::Unknown(UnknownExtension {
+                    typ,
+                    payload: bytes(u, 0..=32)?,
+                })
+            }
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for ClientHelloPayload {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let client_version = ProtocolVersion::from(u16::arbitrary(u)?);
+        let random = Random(u.arbitrary()?);
+        let session_id = SessionId::new(&bytes(u, 0..=32)?).unwrap_or_else(SessionId::empty);
+        let cipher_suites = vec_of(u, 0..=64, |u| Ok(CipherSuite::from(u16::arbitrary(u)?)))?;
+        let compression_methods = vec_of(u, 1..=4, |u| Ok(Compression::from(u8::arbitrary(u)?)))?;
+
+        let mut extensions: Vec<ClientExtension> = vec![];
+        for ext in vec_of(u, 0..=16, ClientExtension::arbitrary)? {
+            if extensions.iter().all(|x| x.get_type() != ext.get_type()) {
+                extensions.push(ext);
+            }
+        }
+
+        let empty_extensions_block = extensions.is_empty() && u.arbitrary()?;
+
+        Ok(Self {
+            client_version,
+            random,
+            session_id,
+            cipher_suites,
+            compression_methods,
+            extensions,
+            empty_extensions_block,
+            wire_extensions: Vec::new(),
+        })
+    }
+}
@@ -0,0 +1,135 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    enums::{CipherSuite, Compression, ProtocolVersion},
+    handshake::{ClientExtension, ClientHelloPayload, Random, SessionId},
+    RewriteError,
+};
+
+//
+/// Builds a ClientHello and encodes it, framed in records or not.
+///
+/// Extensions are encoded in the order they were added, nothing is reordered. Encoding fails
+/// when a length doesn't fit its field instead of writing a malformed ClientHello.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientHelloBuilder {
+    payload: ClientHelloPayload,
+    record_version: ProtocolVersion,
+    records: usize,
+}
+
+impl Default for ClientHelloBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<ClientHelloPayload> for ClientHelloBuilder {
+    fn from(payload: ClientHelloPayload) -> Self {
+        Self {
+            payload,
+            record_version: ProtocolVersion::TLSv1_0,
+            records: 1,
+        }
+    }
+}
+
+impl ClientHelloBuilder {
+    /// A TLS 1.2 ClientHello with a zero random, no session id, no cipher suites, null
+    /// compression and no extensions, in a TLS 1.0 record.
+    pub fn new() -> Self {
+        Self::from(ClientHelloPayload {
+            client_version: ProtocolVersion::TLSv1_2,
+            random: Random([0; 32]),
+            session_id: SessionId::empty(),
+            cipher_suites: vec![],
+            compression_methods: vec![Compression::Null],
+            extensions: vec![],
+            empty_extensions_block: false,
+            wire_extensions: Vec::new(),
+        })
+    }
+
+    pub fn with_client_version(mut self, client_version: ProtocolVersion) -> Self {
+        self.payload.client_version = client_version;
+        self
+    }
+
+    pub fn with_random(mut self, random: [u8; 32]) -> Self {
+        self.payload.random = Random(random);
+        self
+    }
+
+    pub fn with_session_id(mut self, session_id: SessionId) -> Self {
+        self.payload.session_id = session_id;
+        self
+    }
+
+    pub fn with_cipher_suites(
+        mut self,
+        cipher_suites: impl IntoIterator<Item = CipherSuite>,
+    ) -> Self {
+        self.payload.cipher_suites = cipher_suites.into_iter().collect();
+        self
+    }
+
+    pub fn with_compression_methods(
+        mut self,
+        compression_methods: impl IntoIterator<Item = Compression>,
+    ) -> Self {
+        self.payload.compression_methods = compression_methods.into_iter().collect();
+        self
+    }
+
+    /// Appends `extension` after the ones already added.
+    pub fn with_extension(mut self, extension: ClientExtension) -> Self {
+        self.payload.extensions.push(extension);
+        self
+    }
+
+    /// Replaces the extensions.
+    pub fn with_extensions(
+        mut self,
+        extensions: impl IntoIterator<Item = ClientExtension>,
+    ) -> Self {
+        self.payload.extensions = extensions.into_iter().collect();
+        self
+    }
+
+    /// Encodes a zero length extensions block when there are no extensions.
+    pub fn with_empty_extensions_block(mut self, empty_extensions_block: bool) -> Self {
+        self.payload.empty_extensions_block = empty_extensions_block;
+        self
+    }
+
+    /// The legacy version of the records, TLS 1.0 by default like most clients.
+    pub fn with_record_version(mut self, record_version: ProtocolVersion) -> Self {
+        self.record_version = record_version;
+        self
+    }
+
+    /// Fragments the handshake message across `records` records, see `record::encode_records`.
+    pub fn with_records(mut self, records: usize) -> Self {
+        self.records = records;
+        self
+    }
+
+    pub fn payload(&self) -> &ClientHelloPayload {
+        &self.payload
+    }
+
+    pub fn build(self) -> ClientHelloPayload {
+        self.payload
+    }
+
+    /// The handshake message, without record framing.
+    pub fn to_handshake_bytes(&self) -> Result<Vec<u8>, RewriteError> {
+        self.payload.to_handshake_bytes()
+    }
+
+    /// Fails like `ClientHelloPayload::to_record_bytes` when a length doesn't fit its field.
+    pub fn to_record_bytes(&self) -> Result<Vec<u8>, RewriteError> {
+        self.payload
+            .to_record_bytes(self.record_version, self.records)
+    }
+}
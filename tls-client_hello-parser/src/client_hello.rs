@@ -1,20 +1,37 @@
 // Copy from https://github.com/rustls/rustls/blob/v/0.20.8/rustls/src/server/server_conn.rs#L112-L185
 
-use rustls::{internal::msgs::base::PayloadU8, CipherSuite, SignatureScheme};
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
+use crate::{
+    client_hello_ref::ExtensionsRef,
+    codec::PayloadU8,
+    enums::{
+        is_grease_alpn_protocol, version_rank, CipherSuite, Compression, ExtensionType, NamedGroup,
+        PSKKeyExchangeMode, ProtocolVersion, ServerNameType, SignatureScheme,
+    },
+    handshake::{ClientExtension, SessionId},
+    server_name::{normalize_host_name, ServerNameError},
+};
 
 //
 pub struct ClientHello<'a> {
     server_name: Option<webpki::DnsName>,
-    signature_schemes: &'a [SignatureScheme],
-    alpn: Option<&'a Vec<PayloadU8>>,
+    signature_schemes: Option<&'a [SignatureScheme]>,
+    alpn: Option<&'a [PayloadU8]>,
     cipher_suites: &'a [CipherSuite],
+    client_version: ProtocolVersion,
+    random: [u8; 32],
+    session_id: SessionId,
+    compression_methods: &'a [Compression],
+    extensions: &'a [ClientExtension],
+    wire_extensions: &'a [u8],
 }
 
 impl<'a> ClientHello<'a> {
     pub fn new(
         server_name: Option<webpki::DnsName>,
-        signature_schemes: &'a [SignatureScheme],
-        alpn: Option<&'a Vec<PayloadU8>>,
+        signature_schemes: Option<&'a [SignatureScheme]>,
+        alpn: Option<&'a [PayloadU8]>,
         cipher_suites: &'a [CipherSuite],
     ) -> Self {
         ClientHello {
@@ -22,16 +39,109 @@ impl<'a> ClientHello<'a> {
             signature_schemes,
             alpn,
             cipher_suites,
+            client_version: ProtocolVersion::TLSv1_2,
+            random: [0; 32],
+            session_id: SessionId::empty(),
+            compression_methods: &[Compression::Null],
+            extensions: &[],
+            wire_extensions: &[],
         }
     }
 
+    /// Defaults to TLS 1.2, the legacy_version TLS 1.3 clients send.
+    pub fn with_client_version(mut self, client_version: ProtocolVersion) -> Self {
+        self.client_version = client_version;
+        self
+    }
+
+    /// Defaults to zeros.
+    pub fn with_random(mut self, random: [u8; 32]) -> Self {
+        self.random = random;
+        self
+    }
+
+    /// Defaults to empty.
+    pub fn with_session_id(mut self, session_id: SessionId) -> Self {
+        self.session_id = session_id;
+        self
+    }
+
+    /// Defaults to null compression only.
+    pub fn with_compression_methods(mut self, compression_methods: &'a [Compression]) -> Self {
+        self.compression_methods = compression_methods;
+        self
+    }
+
+    pub fn with_extensions(mut self, extensions: &'a [ClientExtension]) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    /// The extensions block `extensions` was read from, with its framing already checked.
+    pub(crate) fn with_wire_extensions(mut self, wire_extensions: &'a [u8]) -> Self {
+        self.wire_extensions = wire_extensions;
+        self
+    }
+
     pub fn server_name(&self) -> Option<&str> {
         self.server_name
             .as_ref()
             .map(<webpki::DnsName as AsRef<str>>::as_ref)
     }
 
-    pub fn signature_schemes(&self) -> &[SignatureScheme] {
+    /// Every entry of the server_name extension as sent, without any validation.
+    pub fn server_names(&self) -> Option<impl Iterator<Item = (ServerNameType, &'a [u8])> + 'a> {
+        match self.find_extension(ExtensionType::ServerName)? {
+            ClientExtension::ServerName(x) => {
+                Some(x.iter().map(|x| (x.typ, x.payload.0.as_slice())))
+            }
+            _ => None,
+        }
+    }
+
+    /// The `host_name` entry of the server_name extension, normalized by `normalize_host_name`.
+    ///
+    /// Unlike `server_name`, the reason a name is rejected is reported. `Ok(None)` means there is
+    /// no `host_name` entry.
+    pub fn normalized_server_name(&self) -> Result<Option<String>, ServerNameError> {
+        self.host_name()?.map(normalize_host_name).transpose()
+    }
+
+    /// Like `normalized_server_name`, but internationalized names are mapped to their lowercase
+    /// A-label form.
+    #[cfg(feature = "idna")]
+    pub fn normalized_idn_server_name(&self) -> Result<Option<String>, ServerNameError> {
+        self.host_name()?
+            .map(crate::server_name::normalize_idn_host_name)
+            .transpose()
+    }
+
+    /// The Unicode form of `normalized_idn_server_name`.
+    #[cfg(feature = "idna")]
+    pub fn unicode_server_name(&self) -> Result<Option<String>, ServerNameError> {
+        self.host_name()?
+            .map(crate::server_name::idn_host_name_to_unicode)
+            .transpose()
+    }
+
+    fn host_name(&self) -> Result<Option<&'a [u8]>, ServerNameError> {
+        let mut host_names = match self.server_names() {
+            Some(x) => x.filter(|(typ, _)| *typ == ServerNameType::HostName),
+            None => return Ok(None),
+        };
+        let name = match host_names.next() {
+            Some((_, name)) => name,
+            None => return Ok(None),
+        };
+        if host_names.next().is_some() {
+            return Err(ServerNameError::MultipleHostNames);
+        }
+        Ok(Some(name))
+    }
+
+    /// `None` when the client didn't send the signature_algorithms extension, as TLS 1.0 and 1.1
+    /// clients may do.
+    pub fn signature_schemes(&self) -> Option<&'a [SignatureScheme]> {
         self.signature_schemes
     }
 
@@ -43,4 +153,220 @@ impl<'a> ClientHello<'a> {
     pub fn cipher_suites(&self) -> &[CipherSuite] {
         self.cipher_suites
     }
+
+    pub fn signature_schemes_without_grease(
+        &self,
+    ) -> Option<impl Iterator<Item = SignatureScheme> + 'a> {
+        self.signature_schemes
+            .map(|x| x.iter().copied().filter(|x| !x.is_grease()))
+    }
+
+    pub fn alpn_without_grease(&self) -> Option<impl Iterator<Item = &'a [u8]>> {
+        self.alpn()
+            .map(|protocols| protocols.filter(|proto| !is_grease_alpn_protocol(proto)))
+    }
+
+    /// Every extension in wire order, as its type and extension data.
+    ///
+    /// The data is borrowed as received, extensions edited or added since are encoded again.
+    pub fn extensions(&self) -> impl Iterator<Item = (ExtensionType, Cow<'a, [u8]>)> + 'a {
+        let mut wire = ExtensionsRef::new(self.wire_extensions);
+        self.extensions.iter().map(move |x| {
+            let payload = match (x, wire.next()) {
+                (ClientExtension::Unknown(x), _) => Cow::Borrowed(x.payload.as_slice()),
+                (x, Some(ext)) if ext.typ == x.get_type() && ext.decode().as_ref() == Ok(x) => {
+                    Cow::Borrowed(ext.payload)
+                }
+                (x, _) => Cow::Owned(x.get_payload_encoding()),
+            };
+            (x.get_type(), payload)
+        })
+    }
+
+    /// The decoded extensions in wire order, extensions the crate doesn't model are
+    /// `ClientExtension::Unknown`.
+    pub fn decoded_extensions(&self) -> &'a [ClientExtension] {
+        self.extensions
+    }
+
+    pub fn find_extension(&self, typ: ExtensionType) -> Option<&'a ClientExtension> {
+        self.extensions.iter().find(|x| x.get_type() == typ)
+    }
+
+    /// Extension types sent more than once, reported once each in the order they are repeated.
+    pub fn duplicate_extensions(&self) -> Vec<ExtensionType> {
+        let mut types = self
+            .extensions
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (u16::from(x.get_type()), i))
+            .collect::<Vec<_>>();
+        types.sort_unstable();
+
+        // The second entry of each run of equal types is the first repeat.
+        let mut repeats = types
+            .windows(2)
+            .enumerate()
+            .filter(|(j, x)| x[0].0 == x[1].0 && (*j == 0 || types[j - 1].0 != x[0].0))
+            .map(|(_, x)| (x[1].1, ExtensionType::from(x[1].0)))
+            .collect::<Vec<_>>();
+        repeats.sort_unstable_by_key(|(i, _)| *i);
+        repeats.into_iter().map(|(_, typ)| typ).collect()
+    }
+
+    /// Extension types missing from `ExtensionType`, GREASE values excluded.
+    pub fn unknown_extensions(&self) -> impl Iterator<Item = ExtensionType> + 'a {
+        self.extensions
+            .iter()
+            .map(|x| x.get_type())
+            .filter(|x| matches!(x, ExtensionType::Unknown(_)) && !x.is_grease())
+    }
+
+    pub fn client_version(&self) -> ProtocolVersion {
+        self.client_version
+    }
+
+    pub fn random(&self) -> &[u8; 32] {
+        &self.random
+    }
+
+    pub fn session_id(&self) -> &[u8] {
+        self.session_id.as_ref()
+    }
+
+    pub fn compression_methods(&self) -> &'a [Compression] {
+        self.compression_methods
+    }
+
+    pub fn supported_versions(&self) -> Option<&'a [ProtocolVersion]> {
+        match self.find_extension(ExtensionType::SupportedVersions)? {
+            ClientExtension::SupportedVersions(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn supported_groups(&self) -> Option<&'a [NamedGroup]> {
+        match self.find_extension(ExtensionType::EllipticCurves)? {
+            ClientExtension::NamedGroups(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// The group and key exchange length of each key share, in the order they were sent.
+    pub fn key_shares(&self) -> Option<impl Iterator<Item = (NamedGroup, usize)> + 'a> {
+        match self.find_extension(ExtensionType::KeyShare)? {
+            ClientExtension::KeyShare(x) => Some(x.iter().map(|x| (x.group, x.payload.0.len()))),
+            _ => None,
+        }
+    }
+
+    pub fn psk_modes(&self) -> Option<&'a [PSKKeyExchangeMode]> {
+        match self.find_extension(ExtensionType::PSKKeyExchangeModes)? {
+            ClientExtension::PresharedKeyModes(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// The highest version offered in supported_versions, falling back to `client_version` when
+    /// the extension is absent or only carries GREASE values.
+    pub fn effective_max_version(&self) -> ProtocolVersion {
+        self.supported_versions()
+            .and_then(|x| {
+                x.iter()
+                    .copied()
+                    .filter(|x| !x.is_grease())
+                    .max_by_key(|x| version_rank(u16::from(*x)))
+            })
+            .unwrap_or(self.client_version)
+    }
+
+    pub fn cipher_suites_without_grease(&self) -> impl Iterator<Item = CipherSuite> + 'a {
+        self.cipher_suites
+            .iter()
+            .copied()
+            .filter(|x| !x.is_grease())
+    }
+}
+
+impl ClientHello<'_> {
+    /// Reports the first reason a rustls server would reject this ClientHello before looking at
+    /// its own configuration.
+    pub fn validate_for_rustls(&self) -> Result<(), ClientHelloError> {
+        // rustls can't even decode a ClientHello whose host_name isn't a valid DNS name, an IP
+        // literal for instance. A trailing dot is fine.
+        if let Some(mut names) = self.server_names() {
+            if names.any(|(typ, name)| {
+                typ == ServerNameType::HostName && webpki::DnsNameRef::try_from_ascii(name).is_err()
+            }) {
+                return Err(ClientHelloError::PeerMisbehaved(
+                    "ClientHello SNI contains an invalid hostname",
+                ));
+            }
+        }
+
+        // https://github.com/rustls/rustls/blob/v/0.20.8/rustls/src/server/hs.rs
+        if !self.compression_methods.contains(&Compression::Null) {
+            return Err(ClientHelloError::PeerIncompatible(
+                "client did not offer Null compression",
+            ));
+        }
+
+        if !self.duplicate_extensions().is_empty() {
+            return Err(ClientHelloError::PeerMisbehaved(
+                "client sent duplicate extensions",
+            ));
+        }
+
+        if let Some(ClientExtension::ServerName(names)) =
+            self.find_extension(ExtensionType::ServerName)
+        {
+            // One flag per name type.
+            let mut seen = [false; 0x100];
+            if names
+                .iter()
+                .any(|x| core::mem::replace(&mut seen[usize::from(u8::from(x.typ))], true))
+            {
+                return Err(ClientHelloError::PeerMisbehaved(
+                    "ClientHello SNI contains duplicate name types",
+                ));
+            }
+
+            if !names.iter().any(|x| x.typ == ServerNameType::HostName) {
+                return Err(ClientHelloError::PeerMisbehaved(
+                    "ClientHello SNI did not contain a hostname",
+                ));
+            }
+        }
+
+        if version_rank(u16::from(self.effective_max_version()))
+            < version_rank(u16::from(ProtocolVersion::TLSv1_2))
+        {
+            return Err(ClientHelloError::PeerIncompatible(
+                "client does not support TLSv1_2 or TLSv1_3",
+            ));
+        }
+
+        // https://github.com/rustls/rustls/blob/v/0.20.8/rustls/src/server/hs.rs#L512
+        if self.signature_schemes.is_none() {
+            return Err(ClientHelloError::PeerIncompatible(
+                "client didn't describe signature schemes",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientHelloError {
+    PeerIncompatible(&'static str),
+    PeerMisbehaved(&'static str),
+}
+impl core::fmt::Display for ClientHelloError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
 }
+#[cfg(feature = "std")]
+impl std::error::Error for ClientHelloError {}
@@ -0,0 +1,325 @@
+use alloc::{borrow::Cow, vec, vec::Vec};
+
+use crate::{
+    codec::{Codec as _, InvalidMessage, Reader},
+    config::{Limit, ParserConfig},
+    enums::{CipherSuite, Compression, ExtensionType, ProtocolVersion, ServerNameType},
+    handshake::{ClientExtension, ClientHelloPayload, Random, SessionId},
+    message::read_client_hello_message,
+    ParseError,
+};
+
+//
+/// A ClientHello that borrows from the input buffer.
+///
+/// Only the framing is checked up front, extensions are decoded on access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientHelloRef<'a> {
+    client_version: ProtocolVersion,
+    random: &'a [u8; 32],
+    session_id: &'a [u8],
+    cipher_suites: &'a [u8],
+    compression_methods: &'a [u8],
+    extensions: &'a [u8],
+    extensions_offset: usize,
+    extensions_block: bool,
+}
+
+impl<'a> ClientHelloRef<'a> {
+    /// Parses the ClientHello at the start of `buf`.
+    ///
+    /// `Ok(None)` means more bytes are needed. A message split across records can't be borrowed
+    /// and fails with `ParseError::MessageFragmented`, pass the body returned by
+    /// `read_client_hello_message` to `read_bytes` instead.
+    pub fn parse(buf: &'a [u8]) -> Result<Option<Self>, ParseError> {
+        match read_client_hello_message(buf)? {
+            Some(msg) => match msg.body {
+                Cow::Borrowed(body) => Self::read_body(body, &ParserConfig::default())
+                    .map(Some)
+                    .map_err(|err| err.map_offset(|x| msg.input_offset(x))),
+                Cow::Owned(_) => Err(ParseError::MessageFragmented),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Reads the body of a ClientHello handshake message.
+    pub fn read_bytes(body: &'a [u8]) -> Result<Self, InvalidMessage> {
+        Self::read_body(body, &ParserConfig::default()).map_err(ParseError::into_invalid_message)
+    }
+
+    pub fn read(r: &mut Reader<'a>) -> Result<Self, InvalidMessage> {
+        Self::read_inner(r, &ParserConfig::default()).map_err(ParseError::into_invalid_message)
+    }
+
+    /// Like `read_bytes`, errors carry their offset in `body`.
+    pub(crate) fn read_body(body: &'a [u8], config: &ParserConfig) -> Result<Self, ParseError> {
+        let mut r = Reader::init(body);
+        let ret = Self::read_inner(&mut r, config)?;
+        r.expect_empty("ClientHelloPayload")
+            .map_err(|err| ParseError::InvalidMessage {
+                err,
+                offset: r.used(),
+            })?;
+        Ok(ret)
+    }
+
+    fn read_inner(r: &mut Reader<'a>, config: &ParserConfig) -> Result<Self, ParseError> {
+        let invalid = |err, offset| ParseError::InvalidMessage { err, offset };
+
+        let client_version = ProtocolVersion::read(r).map_err(|err| invalid(err, r.used()))?;
+
+        let random = r
+            .take(32)
+            .and_then(|x| x.try_into().ok())
+            .ok_or_else(|| invalid(InvalidMessage::MissingData("Random"), r.used()))?;
+
+        let offset = r.used();
+        let session_id_len = usize::from(u8::read(r).map_err(|err| invalid(err, offset))?);
+        if session_id_len > 32 {
+            return Err(invalid(InvalidMessage::InvalidSessionIdLength, offset));
+        }
+        let session_id = r
+            .take(session_id_len)
+            .ok_or_else(|| invalid(InvalidMessage::MissingData("SessionId"), offset))?;
+
+        let offset = r.used();
+        let cipher_suites_len = usize::from(u16::read(r).map_err(|err| invalid(err, offset))?);
+        if cipher_suites_len / 2 > config.max_cipher_suites {
+            return Err(ParseError::LimitExceeded {
+                limit: Limit::CipherSuites,
+                offset: offset + 2 + config.max_cipher_suites * 2,
+            });
+        }
+        let cipher_suites = r
+            .take(cipher_suites_len)
+            .ok_or_else(|| invalid(InvalidMessage::MissingData("CipherSuites"), offset))?;
+        if cipher_suites.len() % 2 != 0 {
+            return Err(invalid(InvalidMessage::MissingData("CipherSuite"), offset));
+        }
+
+        let offset = r.used();
+        let compression_methods_len = usize::from(u8::read(r).map_err(|err| invalid(err, offset))?);
+        let compression_methods = r
+            .take(compression_methods_len)
+            .ok_or_else(|| invalid(InvalidMessage::MissingData("CompressionMethods"), offset))?;
+
+        // Extensions are optional before TLS 1.3.
+        let (extensions, extensions_offset, extensions_block) = if r.any_left() {
+            let offset = r.used();
+            let extensions_len = usize::from(u16::read(r).map_err(|err| invalid(err, offset))?);
+            let extensions_offset = r.used();
+            let extensions = r
+                .take(extensions_len)
+                .ok_or_else(|| invalid(InvalidMessage::MissingData("ClientExtensions"), offset))?;
+
+            let mut r = Reader::init(extensions);
+            let mut n = 0;
+            while r.any_left() {
+                let offset = extensions_offset + r.used();
+                if n == config.max_extensions {
+                    return Err(ParseError::LimitExceeded {
+                        limit: Limit::Extensions,
+                        offset,
+                    });
+                }
+                n += 1;
+                let ext_type = ExtensionType::read(&mut r).map_err(|err| invalid(err, offset))?;
+                let truncated = || ParseError::TruncatedExtension { ext_type, offset };
+                let len = usize::from(u16::read(&mut r).map_err(|_| truncated())?);
+                r.take(len).ok_or_else(truncated)?;
+            }
+
+            (extensions, extensions_offset, true)
+        } else {
+            (&[][..], r.used(), false)
+        };
+
+        Ok(Self {
+            client_version,
+            random,
+            session_id,
+            cipher_suites,
+            compression_methods,
+            extensions,
+            extensions_offset,
+            extensions_block,
+        })
+    }
+
+    pub fn client_version(&self) -> ProtocolVersion {
+        self.client_version
+    }
+
+    pub fn random(&self) -> &'a [u8; 32] {
+        self.random
+    }
+
+    pub fn session_id(&self) -> &'a [u8] {
+        self.session_id
+    }
+
+    pub fn cipher_suites(&self) -> impl Iterator<Item = CipherSuite> + 'a {
+        self.cipher_suites
+            .chunks_exact(2)
+            .map(|x| CipherSuite::from(u16::from_be_bytes([x[0], x[1]])))
+    }
+
+    pub fn compression_methods(&self) -> impl Iterator<Item = Compression> + 'a {
+        self.compression_methods
+            .iter()
+            .map(|x| Compression::from(*x))
+    }
+
+    pub fn extensions(&self) -> ExtensionsRef<'a> {
+        ExtensionsRef::new(self.extensions)
+    }
+
+    pub fn find_extension(&self, typ: ExtensionType) -> Option<ExtensionRef<'a>> {
+        self.extensions().find(|x| x.typ == typ)
+    }
+
+    /// Returns the first `host_name` entry of the server_name extension, without validating it.
+    pub fn sni_host_name(&self) -> Result<Option<&'a [u8]>, InvalidMessage> {
+        let ext = match self.find_extension(ExtensionType::ServerName) {
+            Some(x) => x,
+            None => return Ok(None),
+        };
+
+        let mut r = Reader::init(ext.payload);
+        let len = usize::from(u16::read(&mut r)?);
+        let mut sub = r
+            .sub(len)
+            .ok_or(InvalidMessage::MissingData("ServerName"))?;
+        r.expect_empty("ClientExtension")?;
+
+        let mut ret = None;
+        while sub.any_left() {
+            let typ = ServerNameType::read(&mut sub)?;
+            let len = usize::from(u16::read(&mut sub)?);
+            let name = sub
+                .take(len)
+                .ok_or(InvalidMessage::MissingData("PayloadU16"))?;
+            if typ == ServerNameType::HostName && ret.is_none() {
+                ret = Some(name);
+            }
+        }
+        Ok(ret)
+    }
+
+    /// Like `to_owned`, but applies `config` and errors carry their offset in the body `self` was
+    /// read from.
+    pub(crate) fn to_owned_checked(
+        self,
+        config: &ParserConfig,
+    ) -> Result<ClientHelloPayload, ParseError> {
+        // One bit per extension type, only needed to reject duplicates.
+        let mut seen = match config.reject_duplicate_extensions {
+            true => vec![0_u64; 0x10000 / 64],
+            false => Vec::new(),
+        };
+        let mut extensions = Vec::new();
+
+        let mut offset = self.extensions_offset;
+        for ext in self.extensions() {
+            let ext_type = ext.typ;
+            let decoded = ext.decode().map_err(|err| ParseError::MalformedExtension {
+                ext_type,
+                err,
+                offset,
+            })?;
+
+            if config.reject_duplicate_extensions {
+                let i = usize::from(u16::from(ext_type));
+                if seen[i / 64] & (1 << (i % 64)) != 0 {
+                    return Err(ParseError::DuplicateExtension { ext_type, offset });
+                }
+                seen[i / 64] |= 1 << (i % 64);
+            }
+
+            if let ClientExtension::ServerName(names) = &decoded {
+                if names
+                    .iter()
+                    .any(|x| x.payload.0.len() > config.max_server_name_len)
+                {
+                    return Err(ParseError::LimitExceeded {
+                        limit: Limit::ServerNameLen,
+                        offset,
+                    });
+                }
+            }
+
+            extensions.push(decoded);
+            offset += 2 + 2 + ext.payload.len();
+        }
+
+        Ok(ClientHelloPayload {
+            client_version: self.client_version,
+            random: Random(*self.random),
+            session_id: SessionId::new(self.session_id).ok_or(ParseError::InvalidMessage {
+                err: InvalidMessage::InvalidSessionIdLength,
+                offset: 2 + 32,
+            })?,
+            cipher_suites: self.cipher_suites().collect(),
+            compression_methods: self.compression_methods().collect(),
+            extensions,
+            empty_extensions_block: self.extensions_block && self.extensions.is_empty(),
+            wire_extensions: self.extensions.to_vec(),
+        })
+    }
+
+    pub fn to_owned(&self) -> Result<ClientHelloPayload, InvalidMessage> {
+        Ok(ClientHelloPayload {
+            client_version: self.client_version,
+            random: Random(*self.random),
+            session_id: SessionId::new(self.session_id)
+                .ok_or(InvalidMessage::InvalidSessionIdLength)?,
+            cipher_suites: self.cipher_suites().collect(),
+            compression_methods: self.compression_methods().collect(),
+            extensions: self
+                .extensions()
+                .map(|x| x.decode())
+                .collect::<Result<Vec<_>, _>>()?,
+            empty_extensions_block: self.extensions_block && self.extensions.is_empty(),
+            wire_extensions: self.extensions.to_vec(),
+        })
+    }
+}
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtensionRef<'a> {
+    pub typ: ExtensionType,
+    pub payload: &'a [u8],
+}
+
+impl<'a> ExtensionRef<'a> {
+    pub fn decode(&self) -> Result<ClientExtension, InvalidMessage> {
+        ClientExtension::read_payload(self.typ, self.payload)
+    }
+}
+
+pub struct ExtensionsRef<'a> {
+    r: Reader<'a>,
+}
+
+impl<'a> ExtensionsRef<'a> {
+    /// `extensions` is the content of an extensions block whose framing was already checked.
+    pub(crate) fn new(extensions: &'a [u8]) -> Self {
+        Self {
+            r: Reader::init(extensions),
+        }
+    }
+}
+
+impl<'a> Iterator for ExtensionsRef<'a> {
+    type Item = ExtensionRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The framing was checked in ClientHelloRef::read.
+        let typ = ExtensionType::read(&mut self.r).ok()?;
+        let len = usize::from(u16::read(&mut self.r).ok()?);
+        let payload = self.r.take(len)?;
+        Some(ExtensionRef { typ, payload })
+    }
+}
@@ -0,0 +1,252 @@
+// ref https://github.com/rustls/rustls/blob/v/0.20.8/rustls/src/msgs/codec.rs
+
+use alloc::vec::Vec;
+
+//
+pub struct Reader<'a> {
+    buf: &'a [u8],
+    cursor: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn init(buf: &'a [u8]) -> Self {
+        Self { buf, cursor: 0 }
+    }
+
+    pub fn sub(&mut self, len: usize) -> Option<Reader<'a>> {
+        self.take(len).map(Reader::init)
+    }
+
+    pub fn rest(&mut self) -> &'a [u8] {
+        let rest = &self.buf[self.cursor..];
+        self.cursor = self.buf.len();
+        rest
+    }
+
+    pub fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.left() < len {
+            return None;
+        }
+        let current = self.cursor;
+        self.cursor += len;
+        Some(&self.buf[current..current + len])
+    }
+
+    pub fn any_left(&self) -> bool {
+        self.cursor < self.buf.len()
+    }
+
+    pub fn used(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn left(&self) -> usize {
+        self.buf.len() - self.cursor
+    }
+
+    pub fn expect_empty(&self, name: &'static str) -> Result<(), InvalidMessage> {
+        if self.any_left() {
+            Err(InvalidMessage::TrailingData(name))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+//
+pub trait Codec: Sized {
+    fn read(r: &mut Reader<'_>) -> Result<Self, InvalidMessage>;
+
+    fn read_bytes(bytes: &[u8]) -> Result<Self, InvalidMessage> {
+        let mut r = Reader::init(bytes);
+        let v = Self::read(&mut r)?;
+        r.expect_empty(core::any::type_name::<Self>())?;
+        Ok(v)
+    }
+}
+
+/// Length prefixes are written with `as` casts, only encode what was read or went through the
+/// checks of `ClientHelloPayload::to_handshake_bytes`.
+pub(crate) trait Encode {
+    fn encode(&self, bytes: &mut Vec<u8>);
+
+    fn get_encoding(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.encode(&mut bytes);
+        bytes
+    }
+}
+
+impl Encode for u8 {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.push(*self);
+    }
+}
+
+impl Codec for u8 {
+    fn read(r: &mut Reader<'_>) -> Result<Self, InvalidMessage> {
+        match r.take(1) {
+            Some(&[v]) => Ok(v),
+            _ => Err(InvalidMessage::MissingData("u8")),
+        }
+    }
+}
+
+impl Encode for u16 {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_be_bytes());
+    }
+}
+
+impl Codec for u16 {
+    fn read(r: &mut Reader<'_>) -> Result<Self, InvalidMessage> {
+        match r.take(2) {
+            Some(&[a, b]) => Ok(Self::from_be_bytes([a, b])),
+            _ => Err(InvalidMessage::MissingData("u16")),
+        }
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct u24(pub u32);
+
+impl From<u24> for usize {
+    fn from(v: u24) -> Self {
+        v.0 as Self
+    }
+}
+
+impl Encode for u24 {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.0.to_be_bytes()[1..]);
+    }
+}
+
+impl Codec for u24 {
+    fn read(r: &mut Reader<'_>) -> Result<Self, InvalidMessage> {
+        match r.take(3) {
+            Some(&[a, b, c]) => Ok(Self(u32::from_be_bytes([0, a, b, c]))),
+            _ => Err(InvalidMessage::MissingData("u24")),
+        }
+    }
+}
+
+//
+pub(crate) fn encode_vec_u8<T: Encode>(bytes: &mut Vec<u8>, items: &[T]) {
+    let len_offset = bytes.len();
+    bytes.push(0);
+
+    for i in items {
+        i.encode(bytes);
+    }
+
+    let len = bytes.len() - len_offset - 1;
+    debug_assert!(len <= 0xff);
+    bytes[len_offset] = len as u8;
+}
+
+pub(crate) fn encode_vec_u16<T: Encode>(bytes: &mut Vec<u8>, items: &[T]) {
+    let len_offset = bytes.len();
+    bytes.extend_from_slice(&[0, 0]);
+
+    for i in items {
+        i.encode(bytes);
+    }
+
+    let len = bytes.len() - len_offset - 2;
+    debug_assert!(len <= 0xffff);
+    bytes[len_offset..len_offset + 2].copy_from_slice(&(len as u16).to_be_bytes());
+}
+
+pub(crate) fn read_vec_u8<T: Codec>(
+    r: &mut Reader<'_>,
+    name: &'static str,
+) -> Result<Vec<T>, InvalidMessage> {
+    let len = usize::from(u8::read(r)?);
+    read_vec(r, len, name)
+}
+
+pub(crate) fn read_vec_u16<T: Codec>(
+    r: &mut Reader<'_>,
+    name: &'static str,
+) -> Result<Vec<T>, InvalidMessage> {
+    let len = usize::from(u16::read(r)?);
+    read_vec(r, len, name)
+}
+
+fn read_vec<T: Codec>(
+    r: &mut Reader<'_>,
+    len: usize,
+    name: &'static str,
+) -> Result<Vec<T>, InvalidMessage> {
+    let mut sub = r.sub(len).ok_or(InvalidMessage::MissingData(name))?;
+    let mut ret = Vec::new();
+    while sub.any_left() {
+        ret.push(T::read(&mut sub)?);
+    }
+    Ok(ret)
+}
+
+//
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PayloadU8(pub Vec<u8>);
+
+impl Encode for PayloadU8 {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        debug_assert!(self.0.len() <= 0xff);
+        (self.0.len() as u8).encode(bytes);
+        bytes.extend_from_slice(&self.0);
+    }
+}
+
+impl Codec for PayloadU8 {
+    fn read(r: &mut Reader<'_>) -> Result<Self, InvalidMessage> {
+        let len = usize::from(u8::read(r)?);
+        let body = r
+            .take(len)
+            .ok_or(InvalidMessage::MissingData("PayloadU8"))?;
+        Ok(Self(body.to_vec()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PayloadU16(pub Vec<u8>);
+
+impl Encode for PayloadU16 {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        debug_assert!(self.0.len() <= 0xffff);
+        (self.0.len() as u16).encode(bytes);
+        bytes.extend_from_slice(&self.0);
+    }
+}
+
+impl Codec for PayloadU16 {
+    fn read(r: &mut Reader<'_>) -> Result<Self, InvalidMessage> {
+        let len = usize::from(u16::read(r)?);
+        let body = r
+            .take(len)
+            .ok_or(InvalidMessage::MissingData("PayloadU16"))?;
+        Ok(Self(body.to_vec()))
+    }
+}
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidMessage {
+    MissingData(&'static str),
+    TrailingData(&'static str),
+    InvalidContentType,
+    IllegalVersion,
+    InvalidEmptyPayload,
+    MessageTooLarge,
+    HandshakePayloadTooLarge,
+    InvalidSessionIdLength,
+}
+impl core::fmt::Display for InvalidMessage {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidMessage {}
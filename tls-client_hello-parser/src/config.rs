@@ -0,0 +1,47 @@
+use crate::message::MAX_HANDSHAKE_SIZE;
+
+//
+/// Limits applied by `Parser` to untrusted input.
+///
+/// Apart from `max_records`, the defaults are the largest values the wire format allows. Lower
+/// them to bound the work done per connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParserConfig {
+    /// Largest ClientHello handshake message body, checked as soon as its header is read.
+    pub max_client_hello_size: usize,
+    /// Largest number of records the ClientHello may be split across. Clients send a single
+    /// record, the default leaves room for middleboxes that fragment it.
+    pub max_records: usize,
+    pub max_extensions: usize,
+    pub max_cipher_suites: usize,
+    /// Longest server_name entry, in bytes.
+    pub max_server_name_len: usize,
+    /// Fail with `ParseError::DuplicateExtension` on the first repeated extension type, as rustls
+    /// does. Off by default, the ClientHello is returned and `ClientHello::duplicate_extensions`
+    /// reports them.
+    pub reject_duplicate_extensions: bool,
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        Self {
+            max_client_hello_size: MAX_HANDSHAKE_SIZE,
+            max_records: 64,
+            max_extensions: MAX_HANDSHAKE_SIZE / 4,
+            max_cipher_suites: MAX_HANDSHAKE_SIZE / 2,
+            max_server_name_len: MAX_HANDSHAKE_SIZE,
+            reject_duplicate_extensions: false,
+        }
+    }
+}
+
+//
+/// The `ParserConfig` limit a ClientHello exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    ClientHelloSize,
+    Records,
+    Extensions,
+    CipherSuites,
+    ServerNameLen,
+}
@@ -0,0 +1,424 @@
+//! Human readable dumps of a ClientHello, for debugging handshake failures.
+//!
+//! `ClientHelloPayload::dissect` displays as an indented tree, field by field like Wireshark,
+//! and `Dissection::summary` as a single line for logs.
+//!
+//! ```text
+//! Handshake Protocol: ClientHello
+//!     Handshake Type: ClientHello (1)
+//!     Length: 207
+//!     Version: TLSv1_2 (0x0303)
+//!     ...
+//!     Extension: server_name (0x0000)
+//!         Length: 15
+//!         Server Name Type: HostName (0)
+//!         Server Name: tls.lvh.me
+//! ```
+
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::{self, Debug, Display, Formatter};
+
+use crate::{
+    codec::Encode as _,
+    enums::{is_grease_alpn_protocol, HandshakeType, ServerNameType},
+    handshake::{ClientExtension, ClientHelloPayload},
+    iana::Entry,
+    ja4::Transport,
+    message::HANDSHAKE_HEADER_SIZE,
+    record::{read_record, RecordHeader, HEADER_SIZE},
+};
+
+// Longer unknown extension data is cut, padding is often hundreds of zeros.
+const MAX_DATA_LEN: usize = 64;
+
+//
+#[derive(Debug, Clone)]
+pub struct Dissection<'a> {
+    payload: &'a ClientHelloPayload,
+    records: Vec<RecordHeader>,
+}
+
+impl ClientHelloPayload {
+    /// Displays as an indented tree of the handshake message, see `Dissection::with_records`
+    /// for the record layer.
+    pub fn dissect(&self) -> Dissection<'_> {
+        Dissection {
+            payload: self,
+            records: Vec::new(),
+        }
+    }
+}
+
+impl<'a> Dissection<'a> {
+    /// Also dissects the headers of the records carrying the ClientHello at the start of `buf`,
+    /// the input it was parsed from.
+    pub fn with_records(mut self, mut buf: &[u8]) -> Self {
+        let mut left = HANDSHAKE_HEADER_SIZE + self.payload.get_encoding().len();
+
+        self.records.clear();
+        while left > 0 {
+            let Ok(Some((header, fragment))) = read_record(buf) else {
+                break;
+            };
+            left = left.saturating_sub(fragment.len());
+            buf = &buf[HEADER_SIZE + fragment.len()..];
+            self.records.push(header);
+        }
+        self
+    }
+
+    pub fn summary(&self) -> Summary<'a> {
+        Summary {
+            payload: self.payload,
+        }
+    }
+}
+
+impl Display for Dissection<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let chp = self.payload;
+
+        for header in self.records.iter() {
+            line(f, 0, format_args!("Record Layer"))?;
+            line(
+                f,
+                1,
+                format_args!("Content Type: {}", named_u8(header.typ, false)),
+            )?;
+            line(
+                f,
+                1,
+                format_args!(
+                    "Version: {}",
+                    named_u16(header.version, header.version.is_grease())
+                ),
+            )?;
+            line(f, 1, format_args!("Length: {}", header.len))?;
+        }
+
+        line(f, 0, format_args!("Handshake Protocol: ClientHello"))?;
+        line(
+            f,
+            1,
+            format_args!(
+                "Handshake Type: {}",
+                named_u8(HandshakeType::ClientHello, false)
+            ),
+        )?;
+        line(f, 1, format_args!("Length: {}", chp.get_encoding().len()))?;
+        line(
+            f,
+            1,
+            format_args!(
+                "Version: {}",
+                named_u16(chp.client_version, chp.client_version.is_grease())
+            ),
+        )?;
+        line(f, 1, format_args!("Random: {}", Hex(&chp.random.0)))?;
+        line(
+            f,
+            1,
+            format_args!("Session ID Length: {}", chp.session_id.len()),
+        )?;
+        if !chp.session_id.is_empty() {
+            line(
+                f,
+                1,
+                format_args!("Session ID: {}", Hex(chp.session_id.as_ref())),
+            )?;
+        }
+
+        line(
+            f,
+            1,
+            format_args!("Cipher Suites Length: {}", chp.cipher_suites.len() * 2),
+        )?;
+        line(
+            f,
+            1,
+            format_args!(
+                "Cipher Suites ({})",
+                count(chp.cipher_suites.len(), "suite")
+            ),
+        )?;
+        for x in chp.cipher_suites.iter() {
+            line(
+                f,
+                2,
+                format_args!("Cipher Suite: {}", iana_named(*x, x.is_grease(), x.iana())),
+            )?;
+        }
+
+        line(
+            f,
+            1,
+            format_args!(
+                "Compression Methods Length: {}",
+                chp.compression_methods.len()
+            ),
+        )?;
+        line(
+            f,
+            1,
+            format_args!(
+                "Compression Methods ({})",
+                count(chp.compression_methods.len(), "method")
+            ),
+        )?;
+        for x in chp.compression_methods.iter() {
+            line(
+                f,
+                2,
+                format_args!("Compression Method: {}", named_u8(*x, false)),
+            )?;
+        }
+
+        if chp.extensions.is_empty() && !chp.empty_extensions_block {
+            return Ok(());
+        }
+        let extensions_len: usize = chp
+            .extensions
+            .iter()
+            .map(|x| 4 + x.get_payload_encoding().len())
+            .sum();
+        line(f, 1, format_args!("Extensions Length: {extensions_len}"))?;
+        for ext in chp.extensions.iter() {
+            let typ = ext.get_type();
+            line(
+                f,
+                1,
+                format_args!(
+                    "Extension: {}",
+                    iana_named(typ, typ.is_grease(), typ.iana())
+                ),
+            )?;
+            line(
+                f,
+                2,
+                format_args!("Length: {}", ext.get_payload_encoding().len()),
+            )?;
+            extension(f, ext)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn extension(f: &mut Formatter<'_>, ext: &ClientExtension) -> fmt::Result {
+    match ext {
+        ClientExtension::ECPointFormats(x) => {
+            for x in x.iter() {
+                line(
+                    f,
+                    2,
+                    format_args!("EC Point Format: {}", named_u8(*x, false)),
+                )?;
+            }
+        }
+        ClientExtension::NamedGroups(x) => {
+            for x in x.iter() {
+                line(
+                    f,
+                    2,
+                    format_args!(
+                        "Supported Group: {}",
+                        iana_named(*x, x.is_grease(), x.iana())
+                    ),
+                )?;
+            }
+        }
+        ClientExtension::ServerName(x) => {
+            for x in x.iter() {
+                line(
+                    f,
+                    2,
+                    format_args!("Server Name Type: {}", named_u8(x.typ, false)),
+                )?;
+                // Sent by the client, printed as is only when it can't break the output.
+                let name = &x.payload.0;
+                match x.typ {
+                    ServerNameType::HostName if name.iter().all(|x| x.is_ascii_graphic()) => line(
+                        f,
+                        2,
+                        format_args!("Server Name: {}", String::from_utf8_lossy(name)),
+                    )?,
+                    _ => line(f, 2, format_args!("Server Name: {}", Hex(name)))?,
+                }
+            }
+        }
+        ClientExtension::SignatureAlgorithms(x) => {
+            for x in x.iter() {
+                line(
+                    f,
+                    2,
+                    format_args!(
+                        "Signature Algorithm: {}",
+                        iana_named(*x, x.is_grease(), x.iana())
+                    ),
+                )?;
+            }
+        }
+        ClientExtension::Protocols(x) => {
+            for x in x.iter() {
+                line(f, 2, format_args!("ALPN Protocol: {}", Protocol(&x.0)))?;
+            }
+        }
+        ClientExtension::SupportedVersions(x) => {
+            for x in x.iter() {
+                line(
+                    f,
+                    2,
+                    format_args!("Supported Version: {}", named_u16(*x, x.is_grease())),
+                )?;
+            }
+        }
+        ClientExtension::KeyShare(x) => {
+            for x in x.iter() {
+                line(
+                    f,
+                    2,
+                    format_args!(
+                        "Key Share Entry: Group: {}, Key Exchange Length: {}",
+                        iana_named(x.group, x.group.is_grease(), x.group.iana()),
+                        x.payload.0.len()
+                    ),
+                )?;
+            }
+        }
+        ClientExtension::PresharedKeyModes(x) => {
+            for x in x.iter() {
+                line(
+                    f,
+                    2,
+                    format_args!("PSK Key Exchange Mode: {}", named_u8(*x, x.is_grease())),
+                )?;
+            }
+        }
+        ClientExtension::Unknown(x) => {
+            if x.payload.len() > MAX_DATA_LEN {
+                line(
+                    f,
+                    2,
+                    format_args!("Data: {}...", Hex(&x.payload[..MAX_DATA_LEN])),
+                )?;
+            } else if !x.payload.is_empty() {
+                line(f, 2, format_args!("Data: {}", Hex(&x.payload)))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+//
+/// Displays as a single line, made of `key=value` pairs with `-` for missing values.
+///
+/// ```text
+/// ClientHello version=TLSv1_3 sni=tls.lvh.me alpn=h2,http/1.1 cipher_suites=9 extensions=10 ja4=t13d0910h2_...
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Summary<'a> {
+    payload: &'a ClientHelloPayload,
+}
+
+impl Display for Summary<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let client_hello = self.payload.client_hello();
+
+        write!(
+            f,
+            "ClientHello version={:?} sni={}",
+            client_hello.effective_max_version(),
+            client_hello.server_name().unwrap_or("-")
+        )?;
+
+        f.write_str(" alpn=")?;
+        match client_hello.alpn() {
+            Some(protocols) => {
+                for (i, x) in protocols.enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", Protocol(x))?;
+                }
+            }
+            None => f.write_str("-")?,
+        }
+
+        write!(
+            f,
+            " cipher_suites={} extensions={} ja4={}",
+            self.payload.cipher_suites.len(),
+            self.payload.extensions.len(),
+            self.payload.ja4(Transport::Tcp)
+        )
+    }
+}
+
+//
+fn line(f: &mut Formatter<'_>, depth: usize, args: fmt::Arguments<'_>) -> fmt::Result {
+    for _ in 0..depth {
+        f.write_str("    ")?;
+    }
+    f.write_fmt(args)?;
+    f.write_str("\n")
+}
+
+fn name<T: Debug>(x: T, is_grease: bool) -> String {
+    let name = format!("{x:?}");
+    if is_grease {
+        "GREASE".into()
+    } else if name.starts_with("Unknown(") {
+        "Unknown".into()
+    } else {
+        name
+    }
+}
+
+fn named_u8<T: Debug + Copy + Into<u8>>(x: T, is_grease: bool) -> String {
+    format!("{} ({})", name(x, is_grease), x.into())
+}
+
+fn named_u16<T: Debug + Copy + Into<u16>>(x: T, is_grease: bool) -> String {
+    format!("{} (0x{:04x})", name(x, is_grease), x.into())
+}
+
+// The registry name when there is one, like Wireshark.
+fn iana_named<T: Debug + Copy + Into<u16>>(x: T, is_grease: bool, entry: Option<&Entry>) -> String {
+    match entry {
+        Some(entry) if !is_grease => format!("{} (0x{:04x})", entry.name, x.into()),
+        _ => named_u16(x, is_grease),
+    }
+}
+
+fn count(n: usize, noun: &str) -> String {
+    match n {
+        1 => format!("1 {noun}"),
+        n => format!("{n} {noun}s"),
+    }
+}
+
+struct Hex<'a>(&'a [u8]);
+
+impl Display for Hex<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for b in self.0 {
+            write!(f, "{b:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+// Printable ASCII as is, anything else in hex.
+struct Protocol<'a>(&'a [u8]);
+
+impl Display for Protocol<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if is_grease_alpn_protocol(self.0) {
+            write!(f, "GREASE (0x{})", Hex(self.0))
+        } else if self.0.iter().all(|x| x.is_ascii_graphic()) {
+            f.write_str(&String::from_utf8_lossy(self.0))
+        } else {
+            write!(f, "0x{}", Hex(self.0))
+        }
+    }
+}
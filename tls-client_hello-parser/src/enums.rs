@@ -0,0 +1,373 @@
+// ref https://github.com/rustls/rustls/blob/v/0.20.8/rustls/src/msgs/macros.rs
+// ref https://github.com/rustls/rustls/blob/v/0.20.8/rustls/src/msgs/enums.rs
+
+use alloc::vec::Vec;
+
+use crate::codec::{Codec, Encode, InvalidMessage, Reader};
+
+//
+macro_rules! enum_builder {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident: $uint:ty {
+            $( $var:ident => $val:literal ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[allow(non_camel_case_types)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $( $var, )*
+            Unknown($uint),
+        }
+
+        impl From<$uint> for $name {
+            fn from(x: $uint) -> Self {
+                match x {
+                    $( $val => Self::$var, )*
+                    x => Self::Unknown(x),
+                }
+            }
+        }
+
+        impl From<$name> for $uint {
+            fn from(x: $name) -> Self {
+                match x {
+                    $( $name::$var => $val, )*
+                    $name::Unknown(x) => x,
+                }
+            }
+        }
+
+        impl Encode for $name {
+            fn encode(&self, bytes: &mut Vec<u8>) {
+                <$uint>::from(*self).encode(bytes);
+            }
+        }
+
+        impl Codec for $name {
+            fn read(r: &mut Reader<'_>) -> Result<Self, InvalidMessage> {
+                <$uint>::read(r).map(Self::from)
+            }
+        }
+    };
+}
+
+//
+// https://www.rfc-editor.org/rfc/rfc8701.html
+pub(crate) const fn is_grease_u16(x: u16) -> bool {
+    x & 0x0f0f == 0x0a0a && x >> 8 == x & 0xff
+}
+
+pub(crate) const fn version_rank(x: u16) -> u16 {
+    // DTLS versions count down.
+    if x & 0xff00 == 0xfe00 {
+        0xffff - x
+    } else {
+        x
+    }
+}
+
+/// Reserved ALPN identifiers are the two byte `0x?A 0x?A` values.
+pub fn is_grease_alpn_protocol(protocol: &[u8]) -> bool {
+    match protocol {
+        [a, b] => is_grease_u16(u16::from_be_bytes([*a, *b])),
+        _ => false,
+    }
+}
+
+macro_rules! impl_is_grease {
+    ($($name:ident),* $(,)?) => {
+        $(
+            impl $name {
+                pub fn is_grease(&self) -> bool {
+                    is_grease_u16(u16::from(*self))
+                }
+            }
+        )*
+    };
+}
+
+// https://www.rfc-editor.org/rfc/rfc8701.html#section-2
+impl PSKKeyExchangeMode {
+    pub fn is_grease(&self) -> bool {
+        u8::from(*self) % 0x1f == 0x0b
+    }
+}
+
+impl_is_grease!(
+    ProtocolVersion,
+    ExtensionType,
+    CipherSuite,
+    NamedGroup,
+    SignatureScheme,
+);
+
+//
+enum_builder! {
+    pub enum ContentType: u8 {
+        ChangeCipherSpec => 0x14,
+        Alert => 0x15,
+        Handshake => 0x16,
+        ApplicationData => 0x17,
+        Heartbeat => 0x18,
+    }
+}
+
+enum_builder! {
+    pub enum HandshakeType: u8 {
+        HelloRequest => 0x00,
+        ClientHello => 0x01,
+        ServerHello => 0x02,
+        HelloVerifyRequest => 0x03,
+        NewSessionTicket => 0x04,
+        EndOfEarlyData => 0x05,
+        HelloRetryRequest => 0x06,
+        EncryptedExtensions => 0x08,
+        Certificate => 0x0b,
+        ServerKeyExchange => 0x0c,
+        CertificateRequest => 0x0d,
+        ServerHelloDone => 0x0e,
+        CertificateVerify => 0x0f,
+        ClientKeyExchange => 0x10,
+        Finished => 0x14,
+        CertificateURL => 0x15,
+        CertificateStatus => 0x16,
+        KeyUpdate => 0x18,
+        MessageHash => 0xfe,
+    }
+}
+
+enum_builder! {
+    pub enum AlertDescription: u8 {
+        CloseNotify => 0x00,
+        UnexpectedMessage => 0x0a,
+        BadRecordMac => 0x14,
+        DecryptionFailed => 0x15,
+        RecordOverflow => 0x16,
+        DecompressionFailure => 0x1e,
+        HandshakeFailure => 0x28,
+        NoCertificate => 0x29,
+        BadCertificate => 0x2a,
+        UnsupportedCertificate => 0x2b,
+        CertificateRevoked => 0x2c,
+        CertificateExpired => 0x2d,
+        CertificateUnknown => 0x2e,
+        IllegalParameter => 0x2f,
+        UnknownCA => 0x30,
+        AccessDenied => 0x31,
+        DecodeError => 0x32,
+        DecryptError => 0x33,
+        ExportRestriction => 0x3c,
+        ProtocolVersion => 0x46,
+        InsufficientSecurity => 0x47,
+        InternalError => 0x50,
+        InappropriateFallback => 0x56,
+        UserCanceled => 0x5a,
+        NoRenegotiation => 0x64,
+        MissingExtension => 0x6d,
+        UnsupportedExtension => 0x6e,
+        CertificateUnobtainable => 0x6f,
+        UnrecognisedName => 0x70,
+        BadCertificateStatusResponse => 0x71,
+        BadCertificateHashValue => 0x72,
+        UnknownPSKIdentity => 0x73,
+        CertificateRequired => 0x74,
+        NoApplicationProtocol => 0x78,
+    }
+}
+
+enum_builder! {
+    pub enum ProtocolVersion: u16 {
+        SSLv2 => 0x0200,
+        SSLv3 => 0x0300,
+        TLSv1_0 => 0x0301,
+        TLSv1_1 => 0x0302,
+        TLSv1_2 => 0x0303,
+        TLSv1_3 => 0x0304,
+        DTLSv1_0 => 0xfeff,
+        DTLSv1_2 => 0xfefd,
+        DTLSv1_3 => 0xfefc,
+    }
+}
+
+enum_builder! {
+    pub enum Compression: u8 {
+        Null => 0x00,
+        Deflate => 0x01,
+        LSZ => 0x40,
+    }
+}
+
+enum_builder! {
+    pub enum ServerNameType: u8 {
+        HostName => 0x00,
+    }
+}
+
+// https://www.iana.org/assignments/tls-extensiontype-values/tls-extensiontype-values.xhtml#tls-extensiontype-values-1
+enum_builder! {
+    pub enum ExtensionType: u16 {
+        ServerName => 0x0000,
+        MaxFragmentLength => 0x0001,
+        ClientCertificateUrl => 0x0002,
+        TrustedCAKeys => 0x0003,
+        TruncatedHMAC => 0x0004,
+        StatusRequest => 0x0005,
+        UserMapping => 0x0006,
+        ClientAuthz => 0x0007,
+        ServerAuthz => 0x0008,
+        CertificateType => 0x0009,
+        EllipticCurves => 0x000a,
+        ECPointFormats => 0x000b,
+        SRP => 0x000c,
+        SignatureAlgorithms => 0x000d,
+        UseSRTP => 0x000e,
+        Heartbeat => 0x000f,
+        ALProtocolNegotiation => 0x0010,
+        StatusRequestV2 => 0x0011,
+        SCT => 0x0012,
+        ClientCertificateType => 0x0013,
+        ServerCertificateType => 0x0014,
+        Padding => 0x0015,
+        EncryptThenMac => 0x0016,
+        ExtendedMasterSecret => 0x0017,
+        CompressCertificate => 0x001b,
+        RecordSizeLimit => 0x001c,
+        DelegatedCredential => 0x0022,
+        SessionTicket => 0x0023,
+        PreSharedKey => 0x0029,
+        EarlyData => 0x002a,
+        SupportedVersions => 0x002b,
+        Cookie => 0x002c,
+        PSKKeyExchangeModes => 0x002d,
+        CertificateAuthorities => 0x002f,
+        OIDFilters => 0x0030,
+        PostHandshakeAuth => 0x0031,
+        SignatureAlgorithmsCert => 0x0032,
+        KeyShare => 0x0033,
+        TransportParameters => 0x0039,
+        NextProtocolNegotiation => 0x3374,
+        ApplicationSettings => 0x4469,
+        ChannelId => 0x754f,
+        EncryptedClientHello => 0xfe0d,
+        RenegotiationInfo => 0xff01,
+        TransportParametersDraft => 0xffa5,
+    }
+}
+
+// https://www.iana.org/assignments/tls-parameters/tls-parameters.xhtml#tls-parameters-4
+enum_builder! {
+    pub enum CipherSuite: u16 {
+        TLS_NULL_WITH_NULL_NULL => 0x0000,
+        TLS_RSA_WITH_NULL_MD5 => 0x0001,
+        TLS_RSA_WITH_NULL_SHA => 0x0002,
+        TLS_RSA_EXPORT_WITH_RC4_40_MD5 => 0x0003,
+        TLS_RSA_WITH_RC4_128_MD5 => 0x0004,
+        TLS_RSA_WITH_RC4_128_SHA => 0x0005,
+        TLS_RSA_WITH_DES_CBC_SHA => 0x0009,
+        TLS_RSA_WITH_3DES_EDE_CBC_SHA => 0x000a,
+        TLS_DHE_RSA_WITH_3DES_EDE_CBC_SHA => 0x0016,
+        TLS_RSA_WITH_AES_128_CBC_SHA => 0x002f,
+        TLS_DHE_RSA_WITH_AES_128_CBC_SHA => 0x0033,
+        TLS_RSA_WITH_AES_256_CBC_SHA => 0x0035,
+        TLS_DHE_RSA_WITH_AES_256_CBC_SHA => 0x0039,
+        TLS_RSA_WITH_NULL_SHA256 => 0x003b,
+        TLS_RSA_WITH_AES_128_CBC_SHA256 => 0x003c,
+        TLS_RSA_WITH_AES_256_CBC_SHA256 => 0x003d,
+        TLS_DHE_RSA_WITH_AES_128_CBC_SHA256 => 0x0067,
+        TLS_DHE_RSA_WITH_AES_256_CBC_SHA256 => 0x006b,
+        TLS_RSA_WITH_AES_128_GCM_SHA256 => 0x009c,
+        TLS_RSA_WITH_AES_256_GCM_SHA384 => 0x009d,
+        TLS_DHE_RSA_WITH_AES_128_GCM_SHA256 => 0x009e,
+        TLS_DHE_RSA_WITH_AES_256_GCM_SHA384 => 0x009f,
+        TLS_EMPTY_RENEGOTIATION_INFO_SCSV => 0x00ff,
+        TLS13_AES_128_GCM_SHA256 => 0x1301,
+        TLS13_AES_256_GCM_SHA384 => 0x1302,
+        TLS13_CHACHA20_POLY1305_SHA256 => 0x1303,
+        TLS13_AES_128_CCM_SHA256 => 0x1304,
+        TLS13_AES_128_CCM_8_SHA256 => 0x1305,
+        TLS_FALLBACK_SCSV => 0x5600,
+        TLS_ECDHE_ECDSA_WITH_RC4_128_SHA => 0xc007,
+        TLS_ECDHE_ECDSA_WITH_3DES_EDE_CBC_SHA => 0xc008,
+        TLS_ECDHE_ECDSA_WITH_AES_128_CBC_SHA => 0xc009,
+        TLS_ECDHE_ECDSA_WITH_AES_256_CBC_SHA => 0xc00a,
+        TLS_ECDHE_RSA_WITH_RC4_128_SHA => 0xc011,
+        TLS_ECDHE_RSA_WITH_3DES_EDE_CBC_SHA => 0xc012,
+        TLS_ECDHE_RSA_WITH_AES_128_CBC_SHA => 0xc013,
+        TLS_ECDHE_RSA_WITH_AES_256_CBC_SHA => 0xc014,
+        TLS_ECDHE_ECDSA_WITH_AES_128_CBC_SHA256 => 0xc023,
+        TLS_ECDHE_ECDSA_WITH_AES_256_CBC_SHA384 => 0xc024,
+        TLS_ECDHE_RSA_WITH_AES_128_CBC_SHA256 => 0xc027,
+        TLS_ECDHE_RSA_WITH_AES_256_CBC_SHA384 => 0xc028,
+        TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256 => 0xc02b,
+        TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384 => 0xc02c,
+        TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256 => 0xc02f,
+        TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384 => 0xc030,
+        TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256 => 0xcca8,
+        TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256 => 0xcca9,
+        TLS_DHE_RSA_WITH_CHACHA20_POLY1305_SHA256 => 0xccaa,
+    }
+}
+
+// https://www.iana.org/assignments/tls-parameters/tls-parameters.xhtml#tls-parameters-8
+enum_builder! {
+    pub enum NamedGroup: u16 {
+        secp256r1 => 0x0017,
+        secp384r1 => 0x0018,
+        secp521r1 => 0x0019,
+        X25519 => 0x001d,
+        X448 => 0x001e,
+        brainpoolP256r1tls13 => 0x001f,
+        brainpoolP384r1tls13 => 0x0020,
+        brainpoolP512r1tls13 => 0x0021,
+        FFDHE2048 => 0x0100,
+        FFDHE3072 => 0x0101,
+        FFDHE4096 => 0x0102,
+        FFDHE6144 => 0x0103,
+        FFDHE8192 => 0x0104,
+        MLKEM512 => 0x0200,
+        MLKEM768 => 0x0201,
+        MLKEM1024 => 0x0202,
+        SecP256r1MLKEM768 => 0x11eb,
+        X25519MLKEM768 => 0x11ec,
+        SecP384r1MLKEM1024 => 0x11ed,
+        X25519Kyber768Draft00 => 0x6399,
+    }
+}
+
+// https://www.iana.org/assignments/tls-parameters/tls-parameters.xhtml#tls-parameters-9
+enum_builder! {
+    pub enum ECPointFormat: u8 {
+        Uncompressed => 0x00,
+        ANSIX962CompressedPrime => 0x01,
+        ANSIX962CompressedChar2 => 0x02,
+    }
+}
+
+// https://www.iana.org/assignments/tls-parameters/tls-parameters.xhtml#tls-signaturescheme
+enum_builder! {
+    pub enum SignatureScheme: u16 {
+        RSA_PKCS1_SHA1 => 0x0201,
+        ECDSA_SHA1_Legacy => 0x0203,
+        RSA_PKCS1_SHA256 => 0x0401,
+        ECDSA_NISTP256_SHA256 => 0x0403,
+        RSA_PKCS1_SHA384 => 0x0501,
+        ECDSA_NISTP384_SHA384 => 0x0503,
+        RSA_PKCS1_SHA512 => 0x0601,
+        ECDSA_NISTP521_SHA512 => 0x0603,
+        RSA_PSS_SHA256 => 0x0804,
+        RSA_PSS_SHA384 => 0x0805,
+        RSA_PSS_SHA512 => 0x0806,
+        ED25519 => 0x0807,
+        ED448 => 0x0808,
+    }
+}
+
+// https://www.iana.org/assignments/tls-parameters/tls-parameters.xhtml#tls-pskkeyexchangemode
+enum_builder! {
+    pub enum PSKKeyExchangeMode: u8 {
+        PSK_KE => 0x00,
+        PSK_DHE_KE => 0x01,
+    }
+}
@@ -0,0 +1,406 @@
+// ref https://github.com/rustls/rustls/blob/v/0.20.8/rustls/src/msgs/handshake.rs
+
+use alloc::vec::Vec;
+
+use crate::{
+    client_hello_ref::ClientHelloRef,
+    codec::{
+        encode_vec_u16, encode_vec_u8, read_vec_u16, read_vec_u8, Codec, Encode, InvalidMessage,
+        PayloadU16, PayloadU8, Reader,
+    },
+    enums::{
+        is_grease_alpn_protocol, CipherSuite, Compression, ECPointFormat, ExtensionType,
+        NamedGroup, PSKKeyExchangeMode, ProtocolVersion, ServerNameType, SignatureScheme,
+    },
+};
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Random(pub [u8; 32]);
+
+impl Encode for Random {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.0);
+    }
+}
+
+impl Codec for Random {
+    fn read(r: &mut Reader<'_>) -> Result<Self, InvalidMessage> {
+        let mut v = [0; 32];
+        v.copy_from_slice(r.take(32).ok_or(InvalidMessage::MissingData("Random"))?);
+        Ok(Self(v))
+    }
+}
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SessionId {
+    len: usize,
+    data: [u8; 32],
+}
+
+impl SessionId {
+    pub fn new(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() > 32 {
+            return None;
+        }
+        let mut data = [0; 32];
+        data[..bytes.len()].copy_from_slice(bytes);
+        Some(Self {
+            len: bytes.len(),
+            data,
+        })
+    }
+
+    pub fn empty() -> Self {
+        Self {
+            len: 0,
+            data: [0; 32],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl AsRef<[u8]> for SessionId {
+    fn as_ref(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+}
+
+impl Encode for SessionId {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        (self.len as u8).encode(bytes);
+        bytes.extend_from_slice(self.as_ref());
+    }
+}
+
+impl Codec for SessionId {
+    fn read(r: &mut Reader<'_>) -> Result<Self, InvalidMessage> {
+        let len = usize::from(u8::read(r)?);
+        if len > 32 {
+            return Err(InvalidMessage::InvalidSessionIdLength);
+        }
+        let bytes = r
+            .take(len)
+            .ok_or(InvalidMessage::MissingData("SessionId"))?;
+        Self::new(bytes).ok_or(InvalidMessage::InvalidSessionIdLength)
+    }
+}
+
+//
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ServerName {
+    pub typ: ServerNameType,
+    pub payload: PayloadU16,
+}
+
+impl Encode for ServerName {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.typ.encode(bytes);
+        self.payload.encode(bytes);
+    }
+}
+
+impl Codec for ServerName {
+    fn read(r: &mut Reader<'_>) -> Result<Self, InvalidMessage> {
+        Ok(Self {
+            typ: ServerNameType::read(r)?,
+            payload: PayloadU16::read(r)?,
+        })
+    }
+}
+
+//
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyShareEntry {
+    pub group: NamedGroup,
+    pub payload: PayloadU16,
+}
+
+impl Encode for KeyShareEntry {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.group.encode(bytes);
+        self.payload.encode(bytes);
+    }
+}
+
+impl Codec for KeyShareEntry {
+    fn read(r: &mut Reader<'_>) -> Result<Self, InvalidMessage> {
+        Ok(Self {
+            group: NamedGroup::read(r)?,
+            payload: PayloadU16::read(r)?,
+        })
+    }
+}
+
+//
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnknownExtension {
+    pub typ: ExtensionType,
+    pub payload: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ClientExtension {
+    ECPointFormats(Vec<ECPointFormat>),
+    NamedGroups(Vec<NamedGroup>),
+    ServerName(Vec<ServerName>),
+    SignatureAlgorithms(Vec<SignatureScheme>),
+    Protocols(Vec<PayloadU8>),
+    SupportedVersions(Vec<ProtocolVersion>),
+    KeyShare(Vec<KeyShareEntry>),
+    PresharedKeyModes(Vec<PSKKeyExchangeMode>),
+    Unknown(UnknownExtension),
+}
+
+impl ClientExtension {
+    pub fn get_type(&self) -> ExtensionType {
+        match self {
+            Self::ECPointFormats(_) => ExtensionType::ECPointFormats,
+            Self::NamedGroups(_) => ExtensionType::EllipticCurves,
+            Self::ServerName(_) => ExtensionType::ServerName,
+            Self::SignatureAlgorithms(_) => ExtensionType::SignatureAlgorithms,
+            Self::Protocols(_) => ExtensionType::ALProtocolNegotiation,
+            Self::SupportedVersions(_) => ExtensionType::SupportedVersions,
+            Self::KeyShare(_) => ExtensionType::KeyShare,
+            Self::PresharedKeyModes(_) => ExtensionType::PSKKeyExchangeModes,
+            Self::Unknown(x) => x.typ,
+        }
+    }
+}
+
+impl Encode for ClientExtension {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.get_type().encode(bytes);
+        PayloadU16(self.get_payload_encoding()).encode(bytes);
+    }
+}
+
+impl Codec for ClientExtension {
+    fn read(r: &mut Reader<'_>) -> Result<Self, InvalidMessage> {
+        let typ = ExtensionType::read(r)?;
+        let len = usize::from(u16::read(r)?);
+        let payload = r
+            .take(len)
+            .ok_or(InvalidMessage::MissingData("ClientExtension"))?;
+
+        Self::read_payload(typ, payload)
+    }
+}
+
+impl ClientExtension {
+    /// Encodes the extension data, without the type and length header.
+    pub(crate) fn get_payload_encoding(&self) -> Vec<u8> {
+        let mut sub = Vec::new();
+        match self {
+            Self::ECPointFormats(x) => encode_vec_u8(&mut sub, x),
+            Self::NamedGroups(x) => encode_vec_u16(&mut sub, x),
+            Self::ServerName(x) => encode_vec_u16(&mut sub, x),
+            Self::SignatureAlgorithms(x) => encode_vec_u16(&mut sub, x),
+            Self::Protocols(x) => encode_vec_u16(&mut sub, x),
+            Self::SupportedVersions(x) => encode_vec_u8(&mut sub, x),
+            Self::KeyShare(x) => encode_vec_u16(&mut sub, x),
+            Self::PresharedKeyModes(x) => encode_vec_u8(&mut sub, x),
+            Self::Unknown(x) => sub.extend_from_slice(&x.payload),
+        }
+        sub
+    }
+
+    pub fn read_payload(typ: ExtensionType, payload: &[u8]) -> Result<Self, InvalidMessage> {
+        let mut sub = Reader::init(payload);
+
+        let ext = match typ {
+            ExtensionType::ECPointFormats => {
+                Self::ECPointFormats(read_vec_u8(&mut sub, "ECPointFormats")?)
+            }
+            ExtensionType::EllipticCurves => {
+                Self::NamedGroups(read_vec_u16(&mut sub, "NamedGroups")?)
+            }
+            ExtensionType::ServerName => Self::ServerName(read_vec_u16(&mut sub, "ServerName")?),
+            ExtensionType::SignatureAlgorithms => {
+                Self::SignatureAlgorithms(read_vec_u16(&mut sub, "SignatureAlgorithms")?)
+            }
+            ExtensionType::ALProtocolNegotiation => {
+                Self::Protocols(read_vec_u16(&mut sub, "ALProtocolNegotiation")?)
+            }
+            ExtensionType::SupportedVersions => {
+                Self::SupportedVersions(read_vec_u8(&mut sub, "SupportedVersions")?)
+            }
+            ExtensionType::KeyShare => Self::KeyShare(read_vec_u16(&mut sub, "KeyShare")?),
+            ExtensionType::PSKKeyExchangeModes => {
+                Self::PresharedKeyModes(read_vec_u8(&mut sub, "PSKKeyExchangeModes")?)
+            }
+            _ => Self::Unknown(UnknownExtension {
+                typ,
+                payload: sub.rest().to_vec(),
+            }),
+        };
+
+        sub.expect_empty("ClientExtension")?;
+
+        Ok(ext)
+    }
+}
+
+//
+#[derive(Debug, Clone, Eq)]
+pub struct ClientHelloPayload {
+    pub client_version: ProtocolVersion,
+    pub random: Random,
+    pub session_id: SessionId,
+    pub cipher_suites: Vec<CipherSuite>,
+    pub compression_methods: Vec<Compression>,
+    pub extensions: Vec<ClientExtension>,
+    /// The extensions length field was present with no extensions, `extensions` alone can't
+    /// tell it apart from a ClientHello without extensions.
+    pub empty_extensions_block: bool,
+    /// The extensions block as read, `ClientHello::extensions` borrows the data of every
+    /// extension that still decodes to the same value. Empty when built rather than parsed.
+    pub(crate) wire_extensions: Vec<u8>,
+}
+
+// Two payloads with the same fields are equal however they were built.
+impl PartialEq for ClientHelloPayload {
+    fn eq(&self, other: &Self) -> bool {
+        let Self {
+            client_version,
+            random,
+            session_id,
+            cipher_suites,
+            compression_methods,
+            extensions,
+            empty_extensions_block,
+            wire_extensions: _,
+        } = self;
+        *client_version == other.client_version
+            && *random == other.random
+            && *session_id == other.session_id
+            && *cipher_suites == other.cipher_suites
+            && *compression_methods == other.compression_methods
+            && *extensions == other.extensions
+            && *empty_extensions_block == other.empty_extensions_block
+    }
+}
+
+impl Encode for ClientHelloPayload {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.client_version.encode(bytes);
+        self.random.encode(bytes);
+        self.session_id.encode(bytes);
+        encode_vec_u16(bytes, &self.cipher_suites);
+        encode_vec_u8(bytes, &self.compression_methods);
+
+        if !self.extensions.is_empty() || self.empty_extensions_block {
+            encode_vec_u16(bytes, &self.extensions);
+        }
+    }
+}
+
+impl Codec for ClientHelloPayload {
+    fn read(r: &mut Reader<'_>) -> Result<Self, InvalidMessage> {
+        ClientHelloRef::read(r)?.to_owned()
+    }
+}
+
+impl ClientHelloPayload {
+    pub fn find_extension(&self, typ: ExtensionType) -> Option<&ClientExtension> {
+        self.extensions.iter().find(|x| x.get_type() == typ)
+    }
+
+    pub fn get_sni_extension(&self) -> Option<&[ServerName]> {
+        match self.find_extension(ExtensionType::ServerName)? {
+            ClientExtension::ServerName(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn get_namedgroups_extension(&self) -> Option<&[NamedGroup]> {
+        match self.find_extension(ExtensionType::EllipticCurves)? {
+            ClientExtension::NamedGroups(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn get_ecpoints_extension(&self) -> Option<&[ECPointFormat]> {
+        match self.find_extension(ExtensionType::ECPointFormats)? {
+            ClientExtension::ECPointFormats(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn get_sigalgs_extension(&self) -> Option<&[SignatureScheme]> {
+        match self.find_extension(ExtensionType::SignatureAlgorithms)? {
+            ClientExtension::SignatureAlgorithms(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn get_alpn_extension(&self) -> Option<&[PayloadU8]> {
+        match self.find_extension(ExtensionType::ALProtocolNegotiation)? {
+            ClientExtension::Protocols(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn get_versions_extension(&self) -> Option<&[ProtocolVersion]> {
+        match self.find_extension(ExtensionType::SupportedVersions)? {
+            ClientExtension::SupportedVersions(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn get_keyshare_extension(&self) -> Option<&[KeyShareEntry]> {
+        match self.find_extension(ExtensionType::KeyShare)? {
+            ClientExtension::KeyShare(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn get_psk_modes(&self) -> Option<&[PSKKeyExchangeMode]> {
+        match self.find_extension(ExtensionType::PSKKeyExchangeModes)? {
+            ClientExtension::PresharedKeyModes(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn cipher_suites_without_grease(&self) -> impl Iterator<Item = CipherSuite> + '_ {
+        self.cipher_suites
+            .iter()
+            .copied()
+            .filter(|x| !x.is_grease())
+    }
+
+    pub fn extensions_without_grease(&self) -> impl Iterator<Item = &ClientExtension> {
+        self.extensions.iter().filter(|x| !x.get_type().is_grease())
+    }
+
+    pub fn namedgroups_without_grease(&self) -> Option<impl Iterator<Item = NamedGroup> + '_> {
+        self.get_namedgroups_extension()
+            .map(|x| x.iter().copied().filter(|x| !x.is_grease()))
+    }
+
+    pub fn sigalgs_without_grease(&self) -> Option<impl Iterator<Item = SignatureScheme> + '_> {
+        self.get_sigalgs_extension()
+            .map(|x| x.iter().copied().filter(|x| !x.is_grease()))
+    }
+
+    pub fn alpn_without_grease(&self) -> Option<impl Iterator<Item = &[u8]>> {
+        self.get_alpn_extension().map(|x| {
+            x.iter()
+                .map(|x| x.0.as_slice())
+                .filter(|x| !is_grease_alpn_protocol(x))
+        })
+    }
+
+    pub fn versions_without_grease(&self) -> Option<impl Iterator<Item = ProtocolVersion> + '_> {
+        self.get_versions_extension()
+            .map(|x| x.iter().copied().filter(|x| !x.is_grease()))
+    }
+}
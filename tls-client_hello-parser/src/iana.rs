@@ -0,0 +1,74 @@
+//! IANA TLS registries, generated by `build.rs` from the CSV files in `data/`.
+//!
+//! - https://www.iana.org/assignments/tls-parameters/tls-parameters.xhtml
+//! - https://www.iana.org/assignments/tls-extensiontype-values/tls-extensiontype-values.xhtml
+//!
+//! Lookups are by code point. Reserved ranges, GREASE values included, have an entry,
+//! unassigned code points don't.
+
+use crate::enums::{CipherSuite, ExtensionType, NamedGroup, SignatureScheme};
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Recommended {
+    Yes,
+    No,
+    /// `D`, deprecated or discouraged.
+    Discouraged,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    pub name: &'static str,
+    /// As in the registry, e.g. `[RFC8446][RFC8422]`.
+    pub reference: &'static str,
+    pub recommended: Recommended,
+}
+
+include!(concat!(env!("OUT_DIR"), "/iana.rs"));
+
+fn lookup(registry: &'static [(u16, u16, Entry)], value: u16) -> Option<&'static Entry> {
+    let i = registry.partition_point(|x| x.0 <= value);
+    registry[..i].last().filter(|x| value <= x.1).map(|x| &x.2)
+}
+
+pub fn cipher_suite(value: u16) -> Option<&'static Entry> {
+    lookup(CIPHER_SUITES, value)
+}
+
+pub fn supported_group(value: u16) -> Option<&'static Entry> {
+    lookup(SUPPORTED_GROUPS, value)
+}
+
+pub fn signature_scheme(value: u16) -> Option<&'static Entry> {
+    lookup(SIGNATURE_SCHEMES, value)
+}
+
+pub fn extension_type(value: u16) -> Option<&'static Entry> {
+    lookup(EXTENSION_TYPES, value)
+}
+
+//
+impl CipherSuite {
+    pub fn iana(&self) -> Option<&'static Entry> {
+        cipher_suite(u16::from(*self))
+    }
+}
+
+impl NamedGroup {
+    pub fn iana(&self) -> Option<&'static Entry> {
+        supported_group(u16::from(*self))
+    }
+}
+
+impl SignatureScheme {
+    pub fn iana(&self) -> Option<&'static Entry> {
+        signature_scheme(u16::from(*self))
+    }
+}
+
+impl ExtensionType {
+    pub fn iana(&self) -> Option<&'static Entry> {
+        extension_type(u16::from(*self))
+    }
+}
@@ -0,0 +1,65 @@
+// https://github.com/salesforce/ja3
+
+use alloc::string::String;
+use core::fmt::{Display, Write as _};
+
+use md5::{Digest as _, Md5};
+
+use crate::ClientHelloPayload;
+
+//
+impl ClientHelloPayload {
+    /// `SSLVersion,Cipher,SSLExtension,EllipticCurve,EllipticCurvePointFormat`, GREASE values
+    /// are removed and extensions are kept in the order they were sent.
+    pub fn ja3_string(&self) -> String {
+        let mut s = String::new();
+
+        push_list(&mut s, [u16::from(self.client_version)]);
+        s.push(',');
+        push_list(&mut s, self.cipher_suites_without_grease().map(u16::from));
+        s.push(',');
+        push_list(
+            &mut s,
+            self.extensions_without_grease()
+                .map(|x| u16::from(x.get_type())),
+        );
+        s.push(',');
+        push_list(
+            &mut s,
+            self.namedgroups_without_grease()
+                .into_iter()
+                .flatten()
+                .map(u16::from),
+        );
+        s.push(',');
+        push_list(
+            &mut s,
+            self.get_ecpoints_extension()
+                .unwrap_or_default()
+                .iter()
+                .map(|x| u8::from(*x)),
+        );
+
+        s
+    }
+
+    /// MD5 of `ja3_string`, in lowercase hex.
+    pub fn ja3_hash(&self) -> String {
+        let digest = Md5::digest(self.ja3_string().as_bytes());
+
+        let mut s = String::with_capacity(digest.len() * 2);
+        for b in digest {
+            let _ = write!(s, "{b:02x}");
+        }
+        s
+    }
+}
+
+fn push_list<T: Display>(s: &mut String, list: impl IntoIterator<Item = T>) {
+    for (i, x) in list.into_iter().enumerate() {
+        if i > 0 {
+            s.push('-');
+        }
+        let _ = write!(s, "{x}");
+    }
+}
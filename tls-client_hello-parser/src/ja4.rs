@@ -0,0 +1,186 @@
+// https://github.com/FoxIO-LLC/ja4/blob/main/technical_details/JA4.md
+
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::Write as _;
+
+use sha2::{Digest as _, Sha256};
+
+use crate::{
+    enums::{version_rank, ExtensionType},
+    ClientHelloPayload,
+};
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transport {
+    Tcp,
+    Quic,
+    Dtls,
+}
+
+impl Transport {
+    fn as_char(&self) -> char {
+        match self {
+            Self::Tcp => 't',
+            Self::Quic => 'q',
+            Self::Dtls => 'd',
+        }
+    }
+}
+
+//
+impl ClientHelloPayload {
+    /// `JA4_a_JA4_b_JA4_c`, with the cipher suites and extensions sorted and hashed.
+    pub fn ja4(&self, transport: Transport) -> String {
+        let (ciphers, extensions, signature_algorithms) = self.ja4_lists();
+
+        let mut s = self.ja4_a(transport);
+        s.push('_');
+        push_truncated_hash(&mut s, &ciphers);
+        s.push('_');
+        if extensions.is_empty() {
+            s.push_str(EMPTY_HASH);
+        } else if signature_algorithms.is_empty() {
+            push_truncated_hash(&mut s, &extensions);
+        } else {
+            push_truncated_hash(&mut s, &format!("{extensions}_{signature_algorithms}"));
+        }
+        s
+    }
+
+    /// `JA4_r`, the unhashed form of `ja4`.
+    pub fn ja4_r(&self, transport: Transport) -> String {
+        let (ciphers, extensions, signature_algorithms) = self.ja4_lists();
+
+        let mut s = self.ja4_a(transport);
+        s.push('_');
+        s.push_str(&ciphers);
+        s.push('_');
+        s.push_str(&extensions);
+        if !signature_algorithms.is_empty() {
+            s.push('_');
+            s.push_str(&signature_algorithms);
+        }
+        s
+    }
+
+    fn ja4_a(&self, transport: Transport) -> String {
+        let mut s = String::new();
+
+        s.push(transport.as_char());
+
+        let version = self
+            .versions_without_grease()
+            .and_then(|versions| versions.map(u16::from).max_by_key(|x| version_rank(*x)))
+            .unwrap_or_else(|| u16::from(self.client_version));
+        s.push_str(match version {
+            0x0304 => "13",
+            0x0303 => "12",
+            0x0302 => "11",
+            0x0301 => "10",
+            0x0300 => "s3",
+            0x0002 => "s2",
+            0x0100 => "s1",
+            0xfeff => "d1",
+            0xfefd => "d2",
+            0xfefc => "d3",
+            _ => "00",
+        });
+
+        s.push(if self.get_sni_extension().is_some() {
+            'd'
+        } else {
+            'i'
+        });
+
+        let ciphers_count = self.cipher_suites_without_grease().count();
+        let extensions_count = self.extensions_without_grease().count();
+        let _ = write!(
+            s,
+            "{:02}{:02}",
+            ciphers_count.min(99),
+            extensions_count.min(99)
+        );
+
+        match self
+            .get_alpn_extension()
+            .and_then(|x| x.first())
+            .map(|x| x.0.as_slice())
+        {
+            Some([first, .., last]) | Some([first @ last]) => {
+                if first.is_ascii_alphanumeric() && last.is_ascii_alphanumeric() {
+                    s.push(char::from(*first));
+                    s.push(char::from(*last));
+                } else {
+                    let _ = write!(s, "{:x}{:x}", first >> 4, last & 0x0f);
+                }
+            }
+            _ => s.push_str("00"),
+        }
+
+        s
+    }
+
+    /// Returns the sorted cipher suites, the sorted extensions without SNI and ALPN, and the
+    /// signature algorithms in the order they were sent.
+    fn ja4_lists(&self) -> (String, String, String) {
+        let mut ciphers = self
+            .cipher_suites_without_grease()
+            .map(u16::from)
+            .collect::<Vec<_>>();
+        ciphers.sort_unstable();
+
+        let mut extensions = self
+            .extensions
+            .iter()
+            .map(|x| x.get_type())
+            .filter(|x| {
+                !matches!(
+                    x,
+                    ExtensionType::ServerName | ExtensionType::ALProtocolNegotiation
+                )
+            })
+            .filter(|x| !x.is_grease())
+            .map(u16::from)
+            .collect::<Vec<_>>();
+        extensions.sort_unstable();
+
+        let signature_algorithms = self
+            .sigalgs_without_grease()
+            .into_iter()
+            .flatten()
+            .map(u16::from)
+            .collect::<Vec<_>>();
+
+        (
+            hex_list(&ciphers),
+            hex_list(&extensions),
+            hex_list(&signature_algorithms),
+        )
+    }
+}
+
+const EMPTY_HASH: &str = "000000000000";
+
+fn hex_list(list: &[u16]) -> String {
+    let mut s = String::with_capacity(list.len() * 5);
+    for (i, x) in list.iter().enumerate() {
+        if i > 0 {
+            s.push(',');
+        }
+        let _ = write!(s, "{x:04x}");
+    }
+    s
+}
+
+fn push_truncated_hash(s: &mut String, input: &str) {
+    if input.is_empty() {
+        s.push_str(EMPTY_HASH);
+        return;
+    }
+
+    let digest = Sha256::digest(input.as_bytes());
+    for b in &digest[..6] {
+        let _ = write!(s, "{b:02x}");
+    }
+}
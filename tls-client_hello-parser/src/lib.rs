@@ -1,25 +1,78 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
 use std::io::{Error as IoError, ErrorKind as IoErrorKind, Read};
 
-use rustls::{
-    internal::msgs::{
-        deframer::MessageDeframer,
-        handshake::{ClientHelloPayload as ClientHelloPayloadInner, HandshakePayload},
-        message::{Message, MessagePayload},
-    },
-    ContentType, Error as RustlsError, HandshakeType,
-};
+//
+//
+//
+pub mod codec;
+pub mod enums;
+pub mod handshake;
+pub mod message;
+pub mod record;
+
+pub use handshake::ClientHelloPayload;
+
+pub mod client_hello_ref;
+pub use client_hello_ref::ClientHelloRef;
+
+mod ja3;
+pub mod ja4;
+
+use codec::InvalidMessage;
+use enums::{ContentType, ExtensionType, HandshakeType, ServerNameType};
+use message::{ClientHelloMessage, MessageProgress, MessageReader};
+use record::{is_not_tls, MAX_FRAGMENT_LEN, MAX_WIRE_SIZE};
 
 //
 //
 //
 pub mod client_hello;
-pub use client_hello::ClientHello;
+pub use client_hello::{ClientHello, ClientHelloError};
+
+pub mod server_name;
+pub use server_name::ServerNameError;
+
+pub mod config;
+pub use config::{Limit, ParserConfig};
+
+pub mod builder;
+pub use builder::ClientHelloBuilder;
+
+pub mod rewrite;
+pub use rewrite::RewriteError;
+
+pub mod dissect;
+pub use dissect::Dissection;
+
+pub mod iana;
+
+pub mod security;
+
+pub mod profile;
+
+#[cfg(feature = "rustls")]
+pub mod negotiate;
+#[cfg(feature = "rustls")]
+pub use negotiate::Negotiation;
+
+#[cfg(feature = "serde")]
+pub mod schema;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
 
 //
 //
 //
 pub struct Parser {
-    message_deframer: MessageDeframer,
+    buf: Vec<u8>,
+    config: ParserConfig,
+    reader: MessageReader,
 }
 
 impl Default for Parser {
@@ -30,52 +83,64 @@ impl Default for Parser {
 
 impl Parser {
     pub fn new() -> Self {
+        Self::with_config(ParserConfig::default())
+    }
+
+    pub fn with_config(config: ParserConfig) -> Self {
         Self {
-            message_deframer: MessageDeframer::new(),
+            buf: Vec::new(),
+            config,
+            reader: MessageReader::default(),
+        }
+    }
+
+    /// Appends `bytes` to the buffered input and tries to decode the ClientHello.
+    ///
+    /// `Ok(None)` means more bytes are needed.
+    pub fn parse_bytes(&mut self, bytes: &[u8]) -> Result<Option<ClientHelloPayload>, ParseError> {
+        self.append(bytes);
+
+        self.decode()
+    }
+
+    /// Appends `bytes` to the buffered input and reports how far the ClientHello got, without
+    /// doing any I/O.
+    ///
+    /// The `usize` of `Progress::Done` counts the bytes occupied by the records carrying the
+    /// ClientHello, from the first byte ever fed. Anything past it belongs to the next records.
+    pub fn feed(&mut self, bytes: &[u8]) -> Result<Progress, ParseError> {
+        self.append(bytes);
+
+        if is_not_tls(&self.buf) {
+            return Ok(Progress::NotTls);
+        }
+
+        match self.reader.read(&self.buf, &self.config)? {
+            MessageProgress::NeedMore { at_least } => Ok(Progress::NeedMore { at_least }),
+            MessageProgress::Done(msg) => Ok(Progress::Done(
+                Self::decode_message(&msg, &self.config)?,
+                msg.len,
+            )),
         }
     }
 
+    #[cfg(feature = "std")]
     pub fn parse(&mut self, rd: &mut dyn Read) -> Result<Option<ClientHelloPayload>, ParseError> {
-        let n = self
-            .message_deframer
-            .read(rd)
-            .map_err(ParseError::IoError)?;
-
-        match self
-            .message_deframer
-            .pop()
-            .map_err(ParseError::RustlsError)?
-        {
-            Some(opaque_message) => {
-                let plain_message = opaque_message.into_plain_message();
-                let plain_message_typ = plain_message.typ.to_owned();
-                let message = Message::try_from(plain_message).map_err(ParseError::RustlsError)?;
-                match &message.payload {
-                    MessagePayload::Handshake { parsed, encoded: _ } => match &parsed.payload {
-                        HandshakePayload::ClientHello(chp) => {
-                            let inner = ClientHelloPayloadInner {
-                                client_version: chp.client_version,
-                                random: chp.random.to_owned(),
-                                session_id: chp.session_id,
-                                cipher_suites: chp.cipher_suites.to_owned(),
-                                compression_methods: chp.compression_methods.to_owned(),
-                                extensions: chp.extensions.to_owned(),
-                            };
-                            Ok(Some(ClientHelloPayload(inner)))
-                        }
-                        _ => Err(ParseError::RustlsError(
-                            RustlsError::InappropriateHandshakeMessage {
-                                expect_types: vec![HandshakeType::ClientHello],
-                                got_type: parsed.typ,
-                            },
-                        )),
-                    },
-                    _ => Err(ParseError::RustlsError(RustlsError::InappropriateMessage {
-                        expect_types: vec![ContentType::Handshake],
-                        got_type: plain_message_typ,
-                    })),
-                }
+        let used = self.buf.len();
+
+        self.buf
+            .resize(used + MAX_WIRE_SIZE.min(self.max_buffered() - used), 0);
+        let n = match rd.read(&mut self.buf[used..]) {
+            Ok(n) => n,
+            Err(err) => {
+                self.buf.truncate(used);
+                return Err(ParseError::IoError(err));
             }
+        };
+        self.buf.truncate(used + n);
+
+        match self.decode()? {
+            Some(chp) => Ok(Some(chp)),
             None => {
                 if n == 0 {
                     Err(ParseError::IoError(IoErrorKind::UnexpectedEof.into()))
@@ -85,55 +150,216 @@ impl Parser {
             }
         }
     }
+
+    /// Buffers no more of `bytes` than `max_buffered` allows, the rest can't change the outcome.
+    fn append(&mut self, bytes: &[u8]) {
+        let len = bytes.len().min(self.max_buffered() - self.buf.len());
+        self.buf.extend_from_slice(&bytes[..len]);
+    }
+
+    /// `max_records` full records hold anything the ClientHello may need, the byte after them is
+    /// enough for `MessageReader` to fail with `Limit::Records`.
+    fn max_buffered(&self) -> usize {
+        self.config
+            .max_records
+            .saturating_mul(MAX_WIRE_SIZE)
+            .saturating_add(1)
+    }
+
+    fn decode(&mut self) -> Result<Option<ClientHelloPayload>, ParseError> {
+        match self.reader.read(&self.buf, &self.config)? {
+            MessageProgress::NeedMore { .. } => Ok(None),
+            MessageProgress::Done(msg) => Self::decode_message(&msg, &self.config).map(Some),
+        }
+    }
+
+    fn decode_message(
+        msg: &ClientHelloMessage<'_>,
+        config: &ParserConfig,
+    ) -> Result<ClientHelloPayload, ParseError> {
+        ClientHelloRef::read_body(&msg.body, config)
+            .and_then(|x| x.to_owned_checked(config))
+            .map_err(|err| err.map_offset(|x| msg.input_offset(x)))
+    }
+}
+
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Progress {
+    /// At least `at_least` more bytes are needed, more may be asked for once they are fed.
+    NeedMore {
+        at_least: usize,
+    },
+    Done(ClientHelloPayload, usize),
+    /// The input doesn't start with a record carrying a ClientHello, see `record::is_not_tls`.
+    NotTls,
 }
 
+/// Offsets count bytes from the start of the input, record headers included.
 #[derive(Debug)]
 pub enum ParseError {
+    #[cfg(feature = "std")]
     IoError(IoError),
-    RustlsError(RustlsError),
+    /// A record carrying the ClientHello isn't a handshake record.
+    NotHandshake {
+        content_type: ContentType,
+        offset: usize,
+    },
+    /// The first handshake message isn't a ClientHello.
+    NotClientHello {
+        handshake_type: HandshakeType,
+        offset: usize,
+    },
+    RecordTooLarge {
+        len: usize,
+        offset: usize,
+    },
+    /// The extension runs past the end of the extensions block.
+    TruncatedExtension {
+        ext_type: ExtensionType,
+        offset: usize,
+    },
+    /// The extension is framed correctly but its data can't be decoded.
+    MalformedExtension {
+        ext_type: ExtensionType,
+        err: InvalidMessage,
+        offset: usize,
+    },
+    /// Only with `ParserConfig::reject_duplicate_extensions`.
+    DuplicateExtension {
+        ext_type: ExtensionType,
+        offset: usize,
+    },
+    InvalidMessage {
+        err: InvalidMessage,
+        offset: usize,
+    },
+    /// A `ParserConfig` limit was exceeded, `offset` points at the first item over it.
+    LimitExceeded {
+        limit: Limit,
+        offset: usize,
+    },
+    MessageFragmented,
+}
+
+impl ParseError {
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            #[cfg(feature = "std")]
+            Self::IoError(_) => None,
+            Self::NotHandshake { offset, .. }
+            | Self::NotClientHello { offset, .. }
+            | Self::RecordTooLarge { offset, .. }
+            | Self::TruncatedExtension { offset, .. }
+            | Self::MalformedExtension { offset, .. }
+            | Self::DuplicateExtension { offset, .. }
+            | Self::InvalidMessage { offset, .. }
+            | Self::LimitExceeded { offset, .. } => Some(*offset),
+            Self::MessageFragmented => None,
+        }
+    }
+
+    pub(crate) fn map_offset(mut self, f: impl FnOnce(usize) -> usize) -> Self {
+        match &mut self {
+            #[cfg(feature = "std")]
+            Self::IoError(_) => {}
+            Self::NotHandshake { offset, .. }
+            | Self::NotClientHello { offset, .. }
+            | Self::RecordTooLarge { offset, .. }
+            | Self::TruncatedExtension { offset, .. }
+            | Self::MalformedExtension { offset, .. }
+            | Self::DuplicateExtension { offset, .. }
+            | Self::InvalidMessage { offset, .. }
+            | Self::LimitExceeded { offset, .. } => *offset = f(*offset),
+            Self::MessageFragmented => {}
+        }
+        self
+    }
+
+    /// For the `Codec` style readers, which only see the ClientHello body.
+    pub(crate) fn into_invalid_message(self) -> InvalidMessage {
+        match self {
+            Self::InvalidMessage { err, .. } | Self::MalformedExtension { err, .. } => err,
+            _ => InvalidMessage::MissingData("ClientExtension"),
+        }
+    }
 }
+
 impl core::fmt::Display for ParseError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{self:?}")
+        match self {
+            #[cfg(feature = "std")]
+            Self::IoError(err) => write!(f, "I/O error: {err}"),
+            Self::NotHandshake {
+                content_type,
+                offset,
+            } => write!(
+                f,
+                "record at offset {offset} has content type {content_type:?}, expected Handshake"
+            ),
+            Self::NotClientHello {
+                handshake_type,
+                offset,
+            } => write!(
+                f,
+                "handshake message at offset {offset} is {handshake_type:?}, expected ClientHello"
+            ),
+            Self::RecordTooLarge { len, offset } => write!(
+                f,
+                "record at offset {offset} is {len} bytes long, the limit is {MAX_FRAGMENT_LEN}"
+            ),
+            Self::TruncatedExtension { ext_type, offset } => {
+                write!(f, "extension {ext_type:?} at offset {offset} is truncated")
+            }
+            Self::MalformedExtension {
+                ext_type,
+                err,
+                offset,
+            } => write!(
+                f,
+                "extension {ext_type:?} at offset {offset} is malformed: {err}"
+            ),
+            Self::DuplicateExtension { ext_type, offset } => write!(
+                f,
+                "extension {ext_type:?} at offset {offset} was already sent"
+            ),
+            Self::InvalidMessage { err, offset } => {
+                write!(f, "invalid message at offset {offset}: {err}")
+            }
+            Self::LimitExceeded { limit, offset } => {
+                write!(f, "{limit:?} limit exceeded at offset {offset}")
+            }
+            Self::MessageFragmented => write!(
+                f,
+                "ClientHello spans multiple records and can't be borrowed"
+            ),
+        }
     }
 }
+#[cfg(feature = "std")]
 impl std::error::Error for ParseError {}
 
 //
 //
 //
-#[derive(Debug)]
-pub struct ClientHelloPayload(pub ClientHelloPayloadInner);
-
-impl core::ops::Deref for ClientHelloPayload {
-    type Target = ClientHelloPayloadInner;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-impl core::ops::DerefMut for ClientHelloPayload {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
-    }
-}
-
 impl ClientHelloPayload {
-    pub fn client_hello(&self) -> Result<ClientHello<'_>, RustlsError> {
-        use rustls::internal::msgs::handshake::ConvertServerNameList as _;
-
-        Ok(ClientHello::new(
+    /// Always succeeds, use `ClientHello::validate_for_rustls` to check whether rustls would
+    /// accept it.
+    pub fn client_hello(&self) -> ClientHello<'_> {
+        ClientHello::new(
             self.get_sni_extension()
-                .and_then(|x| x.get_single_hostname())
+                .and_then(|x| x.iter().find(|x| x.typ == ServerNameType::HostName))
+                .and_then(|x| webpki::DnsNameRef::try_from_ascii(&x.payload.0).ok())
                 .map(|x| x.to_owned()),
-            self.get_sigalgs_extension().ok_or_else(|| {
-                // https://github.com/rustls/rustls/blob/v/0.20.8/rustls/src/server/hs.rs#L512
-                RustlsError::PeerIncompatibleError(
-                    "client didn't describe signature schemes".into(),
-                )
-            })?,
+            self.get_sigalgs_extension(),
             self.get_alpn_extension(),
             self.cipher_suites.as_ref(),
-        ))
+        )
+        .with_client_version(self.client_version)
+        .with_random(self.random.0)
+        .with_session_id(self.session_id)
+        .with_compression_methods(&self.compression_methods)
+        .with_extensions(&self.extensions)
+        .with_wire_extensions(&self.wire_extensions)
     }
 }
@@ -0,0 +1,268 @@
+use alloc::{borrow::Cow, vec::Vec};
+
+use crate::{
+    codec::{u24, Encode as _, InvalidMessage},
+    config::{Limit, ParserConfig},
+    enums::{ContentType, HandshakeType},
+    handshake::ClientHelloPayload,
+    record::{read_record, HEADER_SIZE},
+    ParseError,
+};
+
+//
+pub const HANDSHAKE_HEADER_SIZE: usize = 1 + 3;
+pub const MAX_HANDSHAKE_SIZE: usize = 0xffff;
+
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientHelloMessage<'a> {
+    /// The handshake message body, borrowed when the message is carried by a single record.
+    pub body: Cow<'a, [u8]>,
+    /// Number of bytes occupied by the records carrying the message.
+    pub len: usize,
+    /// Input offset and length of each fragment of the handshake message.
+    fragments: Vec<(usize, usize)>,
+}
+
+impl ClientHelloMessage<'_> {
+    /// Maps an offset in `body` to the offset in the input, skipping record headers.
+    pub fn input_offset(&self, body_offset: usize) -> usize {
+        input_offset(&self.fragments, HANDSHAKE_HEADER_SIZE + body_offset)
+    }
+}
+
+fn input_offset(fragments: &[(usize, usize)], message_offset: usize) -> usize {
+    let mut rest = message_offset;
+    for (offset, len) in fragments {
+        if rest < *len {
+            return offset + rest;
+        }
+        rest -= len;
+    }
+    match fragments.last() {
+        Some((offset, len)) => offset + len + rest,
+        None => HEADER_SIZE + rest,
+    }
+}
+
+/// Encodes `chp` as a ClientHello handshake message, header included. `chp` must have been read
+/// or checked by `ClientHelloPayload::to_handshake_bytes`.
+pub(crate) fn encode_client_hello_message(chp: &ClientHelloPayload) -> Vec<u8> {
+    let body = chp.get_encoding();
+
+    let mut bytes = Vec::with_capacity(HANDSHAKE_HEADER_SIZE + body.len());
+    HandshakeType::ClientHello.encode(&mut bytes);
+    u24(body.len() as u32).encode(&mut bytes);
+    bytes.extend_from_slice(&body);
+    bytes
+}
+
+/// Reads the ClientHello handshake message at the start of `buf`, reassembling it when it spans
+/// multiple records.
+///
+/// `Ok(None)` means more bytes are needed.
+pub fn read_client_hello_message(buf: &[u8]) -> Result<Option<ClientHelloMessage<'_>>, ParseError> {
+    // A single pass over `buf`, any number of records is fine.
+    let config = ParserConfig {
+        max_records: usize::MAX,
+        ..Default::default()
+    };
+    match MessageReader::default().read(buf, &config)? {
+        MessageProgress::NeedMore { .. } => Ok(None),
+        MessageProgress::Done(msg) => Ok(Some(msg)),
+    }
+}
+
+pub(crate) enum MessageProgress<'a> {
+    NeedMore { at_least: usize },
+    Done(ClientHelloMessage<'a>),
+}
+
+/// Reassembles the ClientHello from an input that only grows, picking up at the first record it
+/// hasn't seen yet so feeding many small records costs linear time.
+#[derive(Debug, Clone)]
+pub(crate) struct MessageReader {
+    /// Input offset of the next record.
+    offset: usize,
+    header: [u8; HANDSHAKE_HEADER_SIZE],
+    /// `HANDSHAKE_HEADER_SIZE` until the whole header has been seen, header included after.
+    message_len: usize,
+    fragments_len: usize,
+    /// Input offset and length of each fragment.
+    fragments: Vec<(usize, usize)>,
+}
+
+impl Default for MessageReader {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            header: [0; HANDSHAKE_HEADER_SIZE],
+            message_len: HANDSHAKE_HEADER_SIZE,
+            fragments_len: 0,
+            fragments: Vec::new(),
+        }
+    }
+}
+
+impl MessageReader {
+    /// `buf` is the whole input, of which the previous calls saw a prefix.
+    pub(crate) fn read<'a>(
+        &mut self,
+        buf: &'a [u8],
+        config: &ParserConfig,
+    ) -> Result<MessageProgress<'a>, ParseError> {
+        loop {
+            if self.fragments_len >= HANDSHAKE_HEADER_SIZE && self.fragments_len >= self.message_len
+            {
+                return self.done(buf);
+            }
+
+            let offset = self.offset;
+            // Fail on the first byte of the record over the limit, before buffering it.
+            if self.fragments.len() >= config.max_records && buf.len() > offset {
+                return Err(ParseError::LimitExceeded {
+                    limit: Limit::Records,
+                    offset,
+                });
+            }
+
+            let (record_header, fragment) = match read_record(&buf[offset..]) {
+                Ok(Some(x)) => x,
+                Ok(None) => {
+                    let rest = &buf[offset..];
+                    // Check what we have of the handshake header to fail before the record is
+                    // complete.
+                    if self.fragments_len < HANDSHAKE_HEADER_SIZE
+                        && rest.len() > HEADER_SIZE
+                        && ContentType::from(rest[0]) == ContentType::Handshake
+                    {
+                        let mut header = self.header;
+                        let mut fragments = self.fragments.clone();
+                        fragments.push((offset + HEADER_SIZE, rest.len() - HEADER_SIZE));
+                        read_handshake_header(
+                            &mut header,
+                            self.fragments_len,
+                            &rest[HEADER_SIZE..],
+                            &fragments,
+                            config,
+                        )?;
+                    }
+
+                    let at_least = match rest {
+                        [] => HEADER_SIZE + self.message_len - self.fragments_len,
+                        [_, _, _, a, b, ..] => {
+                            HEADER_SIZE + usize::from(u16::from_be_bytes([*a, *b])) - rest.len()
+                        }
+                        _ => HEADER_SIZE - rest.len(),
+                    };
+                    return Ok(MessageProgress::NeedMore { at_least });
+                }
+                Err(InvalidMessage::MessageTooLarge) => {
+                    return Err(ParseError::RecordTooLarge {
+                        len: usize::from(u16::from_be_bytes([buf[offset + 3], buf[offset + 4]])),
+                        offset,
+                    })
+                }
+                Err(err) => return Err(ParseError::InvalidMessage { err, offset }),
+            };
+            if record_header.typ != ContentType::Handshake {
+                return Err(ParseError::NotHandshake {
+                    content_type: record_header.typ,
+                    offset,
+                });
+            }
+
+            self.fragments.push((offset + HEADER_SIZE, fragment.len()));
+            let body_len = match read_handshake_header(
+                &mut self.header,
+                self.fragments_len,
+                fragment,
+                &self.fragments,
+                config,
+            ) {
+                Ok(x) => x,
+                Err(err) => {
+                    self.fragments.pop();
+                    return Err(err);
+                }
+            };
+            self.fragments_len += fragment.len();
+            self.offset += HEADER_SIZE + fragment.len();
+
+            if let Some(body_len) = body_len {
+                self.message_len = HANDSHAKE_HEADER_SIZE + body_len;
+            }
+        }
+    }
+
+    fn done<'a>(&self, buf: &'a [u8]) -> Result<MessageProgress<'a>, ParseError> {
+        if self.fragments_len > self.message_len {
+            return Err(ParseError::InvalidMessage {
+                err: InvalidMessage::TrailingData("HandshakeMessagePayload"),
+                offset: input_offset(&self.fragments, self.message_len),
+            });
+        }
+
+        let body = match self.fragments.as_slice() {
+            [(offset, len)] => Cow::Borrowed(&buf[offset + HANDSHAKE_HEADER_SIZE..offset + len]),
+            fragments => Cow::Owned(
+                fragments
+                    .iter()
+                    .flat_map(|(offset, len)| &buf[*offset..offset + len])
+                    .skip(HANDSHAKE_HEADER_SIZE)
+                    .copied()
+                    .collect(),
+            ),
+        };
+
+        Ok(MessageProgress::Done(ClientHelloMessage {
+            body,
+            len: self.offset,
+            fragments: self.fragments.clone(),
+        }))
+    }
+}
+
+/// Copies what `fragment` holds of the handshake header into `header` and checks it, returning
+/// the body length once the whole header has been seen.
+fn read_handshake_header(
+    header: &mut [u8; HANDSHAKE_HEADER_SIZE],
+    fragments_len: usize,
+    fragment: &[u8],
+    fragment_offsets: &[(usize, usize)],
+    config: &ParserConfig,
+) -> Result<Option<usize>, ParseError> {
+    // The header may itself be split across records.
+    for (i, b) in fragment.iter().enumerate() {
+        match header.get_mut(fragments_len + i) {
+            Some(x) => *x = *b,
+            None => break,
+        }
+    }
+
+    let typ = HandshakeType::from(header[0]);
+    if typ != HandshakeType::ClientHello {
+        return Err(ParseError::NotClientHello {
+            handshake_type: typ,
+            offset: HEADER_SIZE,
+        });
+    }
+    if fragments_len + fragment.len() < HANDSHAKE_HEADER_SIZE {
+        return Ok(None);
+    }
+
+    let body_len = u32::from_be_bytes([0, header[1], header[2], header[3]]) as usize;
+    if body_len > MAX_HANDSHAKE_SIZE {
+        return Err(ParseError::InvalidMessage {
+            err: InvalidMessage::HandshakePayloadTooLarge,
+            offset: input_offset(fragment_offsets, 1),
+        });
+    }
+    if body_len > config.max_client_hello_size {
+        return Err(ParseError::LimitExceeded {
+            limit: Limit::ClientHelloSize,
+            offset: input_offset(fragment_offsets, 1),
+        });
+    }
+    Ok(Some(body_len))
+}
@@ -0,0 +1,251 @@
+//! What a rustls server would negotiate for a ClientHello.
+//!
+//! `ServerConfig` doesn't expose its versions, cipher suites or key exchange groups, so the
+//! ClientHello is fed to a `ServerConnection` built from the config and the outcome is read back
+//! from the connection and from the plaintext part of its first flight. Nothing is sent
+//! anywhere, but the server does the work of a real handshake: certificate resolution, key
+//! generation and, for TLS 1.2, the ServerKeyExchange signature.
+
+use std::{io::Cursor, sync::Arc};
+
+use rustls::{ServerConfig, ServerConnection};
+
+use crate::{
+    client_hello::ClientHello,
+    codec::{u24, Codec, Reader},
+    enums::{
+        AlertDescription, CipherSuite, ContentType, ExtensionType, HandshakeType, NamedGroup,
+        ProtocolVersion,
+    },
+    handshake::{ClientHelloPayload, SessionId},
+    record::RecordHeader,
+    ClientHelloBuilder,
+};
+
+// https://www.rfc-editor.org/rfc/rfc8446#section-4.1.3
+const HELLO_RETRY_REQUEST_RANDOM: [u8; 32] = [
+    0xcf, 0x21, 0xad, 0x74, 0xe5, 0x9a, 0x61, 0x11, 0xbe, 0x1d, 0x8c, 0x02, 0x1e, 0x65, 0xb8, 0x91,
+    0xc2, 0xa2, 0x11, 0x16, 0x7a, 0xbb, 0x8c, 0x5e, 0x07, 0x9e, 0x09, 0xe2, 0xc8, 0xa8, 0x33, 0x9c,
+];
+
+// https://www.rfc-editor.org/rfc/rfc8422#section-5.4
+const NAMED_CURVE: u8 = 3;
+
+//
+#[derive(Debug, Clone, PartialEq)]
+pub enum Negotiation {
+    Accepted(Negotiated),
+    /// TLS 1.3, none of the key shares is usable but the server supports one of the groups. The
+    /// handshake goes on if the client sends a second ClientHello with a share for `group`.
+    HelloRetryRequest {
+        cipher_suite: CipherSuite,
+        group: NamedGroup,
+    },
+    Rejected(Rejection),
+}
+
+impl Negotiation {
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, Self::Accepted(_))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Negotiated {
+    pub version: ProtocolVersion,
+    pub cipher_suite: CipherSuite,
+    /// From the key_share of the ServerHello in TLS 1.3 and the ServerKeyExchange in TLS 1.2,
+    /// `None` when the server sent neither, e.g. on TLS 1.2 resumption.
+    pub group: Option<NamedGroup>,
+    pub alpn: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rejection {
+    pub error: rustls::Error,
+    /// The fatal alert the server sends, if any.
+    pub alert: Option<AlertDescription>,
+}
+
+//
+impl ClientHelloPayload {
+    pub fn negotiate_rustls(&self, config: Arc<ServerConfig>) -> Negotiation {
+        let bytes = match self.to_record_bytes(ProtocolVersion::TLSv1_0, 1) {
+            Ok(bytes) => bytes,
+            // Can't be sent as is, and rustls doesn't join handshake messages over 64KiB.
+            Err(_) => {
+                return Negotiation::Rejected(Rejection {
+                    error: rustls::Error::CorruptMessagePayload(rustls::ContentType::Handshake),
+                    alert: Some(AlertDescription::DecodeError),
+                })
+            }
+        };
+
+        let mut conn = match ServerConnection::new(config) {
+            Ok(conn) => conn,
+            Err(error) => return Negotiation::Rejected(Rejection { error, alert: None }),
+        };
+
+        let mut rd = Cursor::new(&bytes[..]);
+        let mut result = Ok(());
+        while (rd.position() as usize) < bytes.len() {
+            match conn.read_tls(&mut rd) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
+            if let Err(err) = conn.process_new_packets() {
+                result = Err(err);
+                break;
+            }
+        }
+
+        let mut flight = Vec::new();
+        while conn.wants_write() {
+            match conn.write_tls(&mut flight) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
+        }
+        let flight = Flight::read(&flight);
+
+        if let Err(error) = result {
+            // After the ServerHello of TLS 1.3 the alert is encrypted, ALPN is the only check
+            // rustls does that late.
+            let alert = match (flight.alert, &error) {
+                (None, rustls::Error::NoApplicationProtocol) if flight.encrypted => {
+                    Some(AlertDescription::NoApplicationProtocol)
+                }
+                (alert, _) => alert,
+            };
+            return Negotiation::Rejected(Rejection { error, alert });
+        }
+
+        let cipher_suite = conn
+            .negotiated_cipher_suite()
+            .map(|x| CipherSuite::from(x.suite().get_u16()));
+        let version = conn
+            .protocol_version()
+            .map(|x| ProtocolVersion::from(x.get_u16()));
+        match (version, cipher_suite, flight.hello_retry_group) {
+            (_, Some(cipher_suite), Some(group)) => Negotiation::HelloRetryRequest {
+                cipher_suite,
+                group,
+            },
+            (Some(version), Some(cipher_suite), None) => Negotiation::Accepted(Negotiated {
+                version,
+                cipher_suite,
+                group: flight.group,
+                alpn: conn.alpn_protocol().map(|x| x.to_vec()),
+            }),
+            // The ClientHello was consumed without a ServerHello, rustls always answers one.
+            _ => Negotiation::Rejected(Rejection {
+                error: rustls::Error::General("no ServerHello".into()),
+                alert: flight.alert,
+            }),
+        }
+    }
+}
+
+impl ClientHello<'_> {
+    /// The ClientHello is encoded again from its decoded extensions, so pre_shared_key and
+    /// early_data are removed as `rewrite` edits do: the binders can't be checked against bytes
+    /// that weren't sent. A resumption is negotiated as a full handshake, call
+    /// `ClientHelloPayload::negotiate_rustls` on the parsed ClientHello to resume.
+    pub fn negotiate_rustls(&self, config: Arc<ServerConfig>) -> Negotiation {
+        let mut chp = ClientHelloBuilder::new()
+            .with_client_version(self.client_version())
+            .with_random(*self.random())
+            // At most 32 bytes, the view holds a `SessionId`.
+            .with_session_id(SessionId::new(self.session_id()).unwrap_or_else(SessionId::empty))
+            .with_compression_methods(self.compression_methods().iter().copied())
+            .with_cipher_suites(self.cipher_suites().iter().copied())
+            .with_extensions(self.decoded_extensions().iter().cloned())
+            .build();
+        chp.remove_pre_shared_key();
+        chp.negotiate_rustls(config)
+    }
+}
+
+//
+// The plaintext records written by the server, up to the first encrypted one.
+#[derive(Default)]
+struct Flight {
+    group: Option<NamedGroup>,
+    hello_retry_group: Option<NamedGroup>,
+    alert: Option<AlertDescription>,
+    encrypted: bool,
+}
+
+impl Flight {
+    fn read(bytes: &[u8]) -> Self {
+        let mut flight = Self::default();
+
+        let mut handshake = Vec::new();
+        let mut r = Reader::init(bytes);
+        while let Ok(header) = RecordHeader::read(&mut r) {
+            let Some(fragment) = r.take(header.len as usize) else {
+                break;
+            };
+            match header.typ {
+                ContentType::Handshake => handshake.extend_from_slice(fragment),
+                ContentType::Alert => {
+                    if let [_level, description] = fragment {
+                        flight.alert = Some(AlertDescription::from(*description));
+                    }
+                }
+                ContentType::ChangeCipherSpec => {}
+                _ => {
+                    flight.encrypted = true;
+                    break;
+                }
+            }
+        }
+
+        let mut r = Reader::init(&handshake);
+        while let (Ok(typ), Ok(len)) = (HandshakeType::read(&mut r), u24::read(&mut r)) {
+            let Some(mut body) = r.sub(len.0 as usize) else {
+                break;
+            };
+            match typ {
+                HandshakeType::ServerHello => {
+                    flight.read_server_hello(&mut body);
+                }
+                HandshakeType::ServerKeyExchange => {
+                    if let (Ok(NAMED_CURVE), Ok(group)) =
+                        (u8::read(&mut body), NamedGroup::read(&mut body))
+                    {
+                        flight.group = Some(group);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        flight
+    }
+
+    fn read_server_hello(&mut self, r: &mut Reader<'_>) -> Option<()> {
+        r.take(2)?;
+        let random = r.take(32)?;
+        let session_id_len = u8::read(r).ok()?;
+        r.take(session_id_len as usize)?;
+        r.take(2 + 1)?;
+        let extensions_len = u16::read(r).ok()?;
+        let mut r = r.sub(extensions_len as usize)?;
+        while r.any_left() {
+            let typ = ExtensionType::read(&mut r).ok()?;
+            let len = u16::read(&mut r).ok()?;
+            let mut payload = r.sub(len as usize)?;
+            if typ == ExtensionType::KeyShare {
+                // A KeyShareEntry in a ServerHello, only the group in a HelloRetryRequest.
+                let group = NamedGroup::read(&mut payload).ok()?;
+                if random == HELLO_RETRY_REQUEST_RANDOM {
+                    self.hello_retry_group = Some(group);
+                } else {
+                    self.group = Some(group);
+                }
+            }
+        }
+        Some(())
+    }
+}
@@ -0,0 +1,348 @@
+//! Server TLS profiles, and whether a ClientHello can negotiate with a server configured for one.
+//!
+//! `MODERN`, `INTERMEDIATE` and `OLD` are Mozilla's Server Side TLS 5.7 configurations,
+//! https://wiki.mozilla.org/Security/Server_Side_TLS. Their signature schemes are the ones a
+//! server with the recommended certificate types can sign with.
+
+use alloc::vec::Vec;
+
+use crate::{
+    client_hello::ClientHello,
+    enums::{version_rank, CipherSuite, NamedGroup, ProtocolVersion, SignatureScheme},
+    security::{Authentication, KeyExchange, Mac},
+};
+
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub name: &'static str,
+    pub versions: &'static [ProtocolVersion],
+    pub cipher_suites: &'static [CipherSuite],
+    pub groups: &'static [NamedGroup],
+    pub signature_schemes: &'static [SignatureScheme],
+}
+
+const TLS13_CIPHER_SUITES: [CipherSuite; 3] = [
+    CipherSuite::TLS13_AES_128_GCM_SHA256,
+    CipherSuite::TLS13_AES_256_GCM_SHA384,
+    CipherSuite::TLS13_CHACHA20_POLY1305_SHA256,
+];
+
+const INTERMEDIATE_CIPHER_SUITES: [CipherSuite; 12] = [
+    TLS13_CIPHER_SUITES[0],
+    TLS13_CIPHER_SUITES[1],
+    TLS13_CIPHER_SUITES[2],
+    CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256,
+    CipherSuite::TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256,
+    CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384,
+    CipherSuite::TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384,
+    CipherSuite::TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256,
+    CipherSuite::TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256,
+    CipherSuite::TLS_DHE_RSA_WITH_AES_128_GCM_SHA256,
+    CipherSuite::TLS_DHE_RSA_WITH_AES_256_GCM_SHA384,
+    CipherSuite::TLS_DHE_RSA_WITH_CHACHA20_POLY1305_SHA256,
+];
+
+const GROUPS: [NamedGroup; 3] = [
+    NamedGroup::X25519,
+    NamedGroup::secp256r1,
+    NamedGroup::secp384r1,
+];
+
+const ECDSA_SIGNATURE_SCHEMES: [SignatureScheme; 3] = [
+    SignatureScheme::ECDSA_NISTP256_SHA256,
+    SignatureScheme::ECDSA_NISTP384_SHA384,
+    SignatureScheme::ECDSA_NISTP521_SHA512,
+];
+
+const INTERMEDIATE_SIGNATURE_SCHEMES: [SignatureScheme; 9] = [
+    ECDSA_SIGNATURE_SCHEMES[0],
+    ECDSA_SIGNATURE_SCHEMES[1],
+    ECDSA_SIGNATURE_SCHEMES[2],
+    SignatureScheme::RSA_PSS_SHA256,
+    SignatureScheme::RSA_PSS_SHA384,
+    SignatureScheme::RSA_PSS_SHA512,
+    SignatureScheme::RSA_PKCS1_SHA256,
+    SignatureScheme::RSA_PKCS1_SHA384,
+    SignatureScheme::RSA_PKCS1_SHA512,
+];
+
+/// TLS 1.3 only, ECDSA certificates.
+pub const MODERN: Profile = Profile {
+    name: "modern",
+    versions: &[ProtocolVersion::TLSv1_3],
+    cipher_suites: &TLS13_CIPHER_SUITES,
+    groups: &GROUPS,
+    signature_schemes: &ECDSA_SIGNATURE_SCHEMES,
+};
+
+/// TLS 1.2 and 1.3 with AEAD suites, ECDSA or RSA certificates.
+pub const INTERMEDIATE: Profile = Profile {
+    name: "intermediate",
+    versions: &[ProtocolVersion::TLSv1_3, ProtocolVersion::TLSv1_2],
+    cipher_suites: &INTERMEDIATE_CIPHER_SUITES,
+    groups: &GROUPS,
+    signature_schemes: &INTERMEDIATE_SIGNATURE_SCHEMES,
+};
+
+/// TLS 1.0 to 1.3 with CBC suites and 3DES, RSA certificates signed with SHA-256.
+///
+/// Mozilla's configuration also lists the ECDHE_ECDSA suites, they are left out as no ECDSA
+/// scheme goes with the RSA certificate.
+pub const OLD: Profile = Profile {
+    name: "old",
+    versions: &[
+        ProtocolVersion::TLSv1_3,
+        ProtocolVersion::TLSv1_2,
+        ProtocolVersion::TLSv1_1,
+        ProtocolVersion::TLSv1_0,
+    ],
+    cipher_suites: &[
+        INTERMEDIATE_CIPHER_SUITES[0],
+        INTERMEDIATE_CIPHER_SUITES[1],
+        INTERMEDIATE_CIPHER_SUITES[2],
+        INTERMEDIATE_CIPHER_SUITES[4],
+        INTERMEDIATE_CIPHER_SUITES[6],
+        INTERMEDIATE_CIPHER_SUITES[8],
+        INTERMEDIATE_CIPHER_SUITES[9],
+        INTERMEDIATE_CIPHER_SUITES[10],
+        INTERMEDIATE_CIPHER_SUITES[11],
+        CipherSuite::TLS_ECDHE_RSA_WITH_AES_128_CBC_SHA256,
+        CipherSuite::TLS_ECDHE_RSA_WITH_AES_128_CBC_SHA,
+        CipherSuite::TLS_ECDHE_RSA_WITH_AES_256_CBC_SHA384,
+        CipherSuite::TLS_ECDHE_RSA_WITH_AES_256_CBC_SHA,
+        CipherSuite::TLS_DHE_RSA_WITH_AES_128_CBC_SHA256,
+        CipherSuite::TLS_DHE_RSA_WITH_AES_256_CBC_SHA256,
+        CipherSuite::TLS_RSA_WITH_AES_128_GCM_SHA256,
+        CipherSuite::TLS_RSA_WITH_AES_256_GCM_SHA384,
+        CipherSuite::TLS_RSA_WITH_AES_128_CBC_SHA256,
+        CipherSuite::TLS_RSA_WITH_AES_256_CBC_SHA256,
+        CipherSuite::TLS_RSA_WITH_AES_128_CBC_SHA,
+        CipherSuite::TLS_RSA_WITH_AES_256_CBC_SHA,
+        CipherSuite::TLS_RSA_WITH_3DES_EDE_CBC_SHA,
+    ],
+    groups: &GROUPS,
+    signature_schemes: &[
+        SignatureScheme::RSA_PSS_SHA256,
+        SignatureScheme::RSA_PSS_SHA384,
+        SignatureScheme::RSA_PSS_SHA512,
+        SignatureScheme::RSA_PKCS1_SHA256,
+        SignatureScheme::RSA_PKCS1_SHA384,
+        SignatureScheme::RSA_PKCS1_SHA512,
+        SignatureScheme::RSA_PKCS1_SHA1,
+    ],
+};
+
+//
+/// The profile values the ClientHello offers none of, for each requirement that prevents the
+/// handshake. All empty when the handshake is possible.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Evaluation {
+    pub missing_versions: Vec<ProtocolVersion>,
+    pub missing_cipher_suites: Vec<CipherSuite>,
+    pub missing_groups: Vec<NamedGroup>,
+    pub missing_signature_schemes: Vec<SignatureScheme>,
+}
+
+impl Evaluation {
+    pub fn is_compatible(&self) -> bool {
+        self.missing_versions.is_empty()
+            && self.missing_cipher_suites.is_empty()
+            && self.missing_groups.is_empty()
+            && self.missing_signature_schemes.is_empty()
+    }
+}
+
+impl Profile {
+    /// Whether a server configured with this profile could complete a handshake with the client.
+    ///
+    /// Every version and suite both sides have is tried, the handshake is possible if one of them
+    /// also has a common group when it needs one, and a common signature scheme.
+    pub fn evaluate(&self, client_hello: &ClientHello<'_>) -> Evaluation {
+        let versions = self
+            .versions
+            .iter()
+            .copied()
+            .filter(|x| offers_version(client_hello, *x))
+            .collect::<Vec<_>>();
+        // Without a common version, suites are checked against all of the profile ones.
+        let candidate_versions = if versions.is_empty() {
+            self.versions
+        } else {
+            &versions[..]
+        };
+
+        let mut candidates = Vec::new();
+        for version in candidate_versions.iter().copied() {
+            for cipher_suite in self.cipher_suites.iter().copied() {
+                if client_hello.cipher_suites().contains(&cipher_suite)
+                    && usable_with(cipher_suite, version)
+                {
+                    candidates.push((version, cipher_suite));
+                }
+            }
+        }
+
+        let group_ok = |(version, cipher_suite): (ProtocolVersion, CipherSuite)| {
+            !needs_group(cipher_suite, version)
+                || match client_hello.supported_groups() {
+                    Some(groups) => groups.iter().any(|x| self.groups.contains(x)),
+                    // Any curve goes, RFC 8422 section 4.
+                    None => version != ProtocolVersion::TLSv1_3,
+                }
+        };
+        let signature_ok = |(version, cipher_suite): (ProtocolVersion, CipherSuite)| {
+            self.signature_schemes_for(version, cipher_suite)
+                .any(|x| offers_signature_scheme(client_hello, version, x))
+        };
+
+        let mut evaluation = Evaluation::default();
+        if !versions.is_empty() && candidates.iter().any(|x| group_ok(*x) && signature_ok(*x)) {
+            return evaluation;
+        }
+
+        if versions.is_empty() {
+            evaluation.missing_versions = self.versions.to_vec();
+        }
+        if candidates.is_empty() {
+            evaluation.missing_cipher_suites = self
+                .cipher_suites
+                .iter()
+                .copied()
+                .filter(|x| candidate_versions.iter().any(|v| usable_with(*x, *v)))
+                .collect();
+            return evaluation;
+        }
+
+        // What no candidate has, or else what the one with the best version lacks.
+        let best = candidates
+            .iter()
+            .copied()
+            .max_by_key(|(v, _)| version_rank(u16::from(*v)))
+            .expect("candidates");
+        let (groups_missing, signature_schemes_missing) = match (
+            !candidates.iter().any(|x| group_ok(*x)),
+            !candidates.iter().any(|x| signature_ok(*x)),
+        ) {
+            (false, false) => (!group_ok(best), !signature_ok(best)),
+            x => x,
+        };
+        if groups_missing {
+            evaluation.missing_groups = self.groups.to_vec();
+        }
+        if signature_schemes_missing {
+            evaluation.missing_signature_schemes =
+                self.signature_schemes_for(best.0, best.1).collect();
+        }
+
+        evaluation
+    }
+
+    // TLS 1.3 dropped the SHA-1 and PKCS#1 schemes from handshake signatures. Before it, the
+    // suite fixes the certificate type and so the schemes the server can sign with.
+    fn signature_schemes_for(
+        &self,
+        version: ProtocolVersion,
+        cipher_suite: CipherSuite,
+    ) -> impl Iterator<Item = SignatureScheme> + '_ {
+        let authentication = cipher_suite
+            .classify()
+            .and_then(|x| x.info())
+            .map(|x| x.authentication);
+        self.signature_schemes.iter().copied().filter(move |x| {
+            let rsa = matches!(
+                x,
+                SignatureScheme::RSA_PKCS1_SHA1
+                    | SignatureScheme::RSA_PKCS1_SHA256
+                    | SignatureScheme::RSA_PKCS1_SHA384
+                    | SignatureScheme::RSA_PKCS1_SHA512
+                    | SignatureScheme::RSA_PSS_SHA256
+                    | SignatureScheme::RSA_PSS_SHA384
+                    | SignatureScheme::RSA_PSS_SHA512
+            );
+            let ecdsa = matches!(
+                x,
+                SignatureScheme::ECDSA_SHA1_Legacy
+                    | SignatureScheme::ECDSA_NISTP256_SHA256
+                    | SignatureScheme::ECDSA_NISTP384_SHA384
+                    | SignatureScheme::ECDSA_NISTP521_SHA512
+                    | SignatureScheme::ED25519
+                    | SignatureScheme::ED448
+            );
+            match (version, authentication) {
+                (ProtocolVersion::TLSv1_3, _) => !matches!(
+                    x,
+                    SignatureScheme::RSA_PKCS1_SHA1
+                        | SignatureScheme::ECDSA_SHA1_Legacy
+                        | SignatureScheme::RSA_PKCS1_SHA256
+                        | SignatureScheme::RSA_PKCS1_SHA384
+                        | SignatureScheme::RSA_PKCS1_SHA512
+                ),
+                (_, Some(Authentication::Rsa)) => rsa,
+                // RFC 8422 section 5.1.1, EdDSA certificates go with the ECDSA suites.
+                (_, Some(Authentication::Ecdsa)) => ecdsa,
+                _ => true,
+            }
+        })
+    }
+}
+
+// Without supported_versions, every version up to the legacy one, TLS 1.2 at most.
+fn offers_version(client_hello: &ClientHello<'_>, version: ProtocolVersion) -> bool {
+    match client_hello.supported_versions() {
+        Some(versions) => versions.contains(&version),
+        None => {
+            let rank = version_rank(u16::from(version));
+            rank <= version_rank(u16::from(client_hello.client_version()))
+                && rank <= version_rank(u16::from(ProtocolVersion::TLSv1_2))
+        }
+    }
+}
+
+// Before TLS 1.2, signature schemes aren't negotiated. In TLS 1.2 their absence means SHA-1,
+// RFC 5246 section 7.4.1.4.1.
+fn offers_signature_scheme(
+    client_hello: &ClientHello<'_>,
+    version: ProtocolVersion,
+    scheme: SignatureScheme,
+) -> bool {
+    match (version, client_hello.signature_schemes()) {
+        (ProtocolVersion::TLSv1_3 | ProtocolVersion::TLSv1_2, Some(schemes)) => {
+            schemes.contains(&scheme)
+        }
+        (ProtocolVersion::TLSv1_3, None) => false,
+        (ProtocolVersion::TLSv1_2, None) => matches!(
+            scheme,
+            SignatureScheme::RSA_PKCS1_SHA1 | SignatureScheme::ECDSA_SHA1_Legacy
+        ),
+        _ => true,
+    }
+}
+
+// TLS 1.3 suites only with TLS 1.3, AEAD and SHA-2 suites from TLS 1.2.
+fn usable_with(cipher_suite: CipherSuite, version: ProtocolVersion) -> bool {
+    let Some(info) = cipher_suite.classify().and_then(|x| x.info()) else {
+        return version != ProtocolVersion::TLSv1_3;
+    };
+    match info.key_exchange {
+        KeyExchange::Any => version == ProtocolVersion::TLSv1_3,
+        _ if info.is_aead() || matches!(info.mac, Mac::Sha256 | Mac::Sha384) => {
+            version == ProtocolVersion::TLSv1_2
+        }
+        _ => version != ProtocolVersion::TLSv1_3,
+    }
+}
+
+fn needs_group(cipher_suite: CipherSuite, version: ProtocolVersion) -> bool {
+    version == ProtocolVersion::TLSv1_3
+        || cipher_suite
+            .classify()
+            .and_then(|x| x.info())
+            .is_some_and(|x| matches!(x.key_exchange, KeyExchange::Ecdhe | KeyExchange::EcdhePsk))
+}
+
+impl ClientHello<'_> {
+    pub fn evaluate(&self, profile: &Profile) -> Evaluation {
+        profile.evaluate(self)
+    }
+}
@@ -0,0 +1,129 @@
+// ref https://github.com/rustls/rustls/blob/v/0.20.8/rustls/src/msgs/message.rs
+
+use alloc::vec::Vec;
+
+use crate::{
+    codec::{Codec, Encode, InvalidMessage, Reader},
+    enums::{ContentType, ProtocolVersion},
+};
+
+//
+pub const HEADER_SIZE: usize = 1 + 2 + 2;
+pub const MAX_FRAGMENT_LEN: usize = 16384;
+pub const MAX_WIRE_SIZE: usize = MAX_FRAGMENT_LEN + HEADER_SIZE;
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordHeader {
+    pub typ: ContentType,
+    pub version: ProtocolVersion,
+    pub len: u16,
+}
+
+impl Encode for RecordHeader {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.typ.encode(bytes);
+        self.version.encode(bytes);
+        self.len.encode(bytes);
+    }
+}
+
+impl Codec for RecordHeader {
+    fn read(r: &mut Reader<'_>) -> Result<Self, InvalidMessage> {
+        let typ = ContentType::read(r)?;
+        if let ContentType::Unknown(_) = typ {
+            return Err(InvalidMessage::InvalidContentType);
+        }
+
+        let version = ProtocolVersion::read(r)?;
+        if u16::from(version) & 0xff00 != 0x0300 {
+            return Err(InvalidMessage::IllegalVersion);
+        }
+
+        let len = u16::read(r)?;
+        if typ != ContentType::ApplicationData && len == 0 {
+            return Err(InvalidMessage::InvalidEmptyPayload);
+        }
+        if usize::from(len) > MAX_FRAGMENT_LEN {
+            return Err(InvalidMessage::MessageTooLarge);
+        }
+
+        Ok(Self { typ, version, len })
+    }
+}
+
+//
+/// Returns the header and the fragment of the first complete record in `buf`,
+/// or `None` if more bytes are needed.
+pub fn read_record(buf: &[u8]) -> Result<Option<(RecordHeader, &[u8])>, InvalidMessage> {
+    if buf.len() < HEADER_SIZE {
+        return Ok(None);
+    }
+
+    let mut r = Reader::init(buf);
+    let header = RecordHeader::read(&mut r)?;
+
+    match r.take(usize::from(header.len)) {
+        Some(fragment) => Ok(Some((header, fragment))),
+        None => Ok(None),
+    }
+}
+
+//
+/// Frames `payload` into `records` records of nearly equal length, more if one of them would be
+/// over `MAX_FRAGMENT_LEN` and fewer if `payload` is shorter than `records`.
+pub fn encode_records(
+    typ: ContentType,
+    version: ProtocolVersion,
+    payload: &[u8],
+    records: usize,
+) -> Vec<u8> {
+    let records = records
+        .max(payload.len().div_ceil(MAX_FRAGMENT_LEN))
+        .min(payload.len())
+        .max(1);
+    let (len, rem) = (payload.len() / records, payload.len() % records);
+
+    let mut bytes = Vec::with_capacity(payload.len() + records * HEADER_SIZE);
+    let mut rest = payload;
+    for i in 0..records {
+        let (fragment, next) = rest.split_at(len + usize::from(i < rem));
+        RecordHeader {
+            typ,
+            version,
+            len: fragment.len() as u16,
+        }
+        .encode(&mut bytes);
+        bytes.extend_from_slice(fragment);
+        rest = next;
+    }
+    bytes
+}
+
+//
+/// Checks the first bytes of a stream against the header of a record carrying a ClientHello.
+///
+/// `true` is definitive and can be returned from the first byte on, `false` means the bytes seen
+/// so far are plausible.
+pub fn is_not_tls(prefix: &[u8]) -> bool {
+    // Handshake content type.
+    if matches!(prefix.first(), Some(x) if *x != 0x16) {
+        return true;
+    }
+    // Legacy record version, SSL 3.0 to TLS 1.3.
+    if matches!(prefix.get(1), Some(x) if *x != 0x03) {
+        return true;
+    }
+    if matches!(prefix.get(2), Some(x) if *x > 0x04) {
+        return true;
+    }
+    match (prefix.get(3), prefix.get(4)) {
+        (Some(a), Some(b)) => {
+            let len = usize::from(u16::from_be_bytes([*a, *b]));
+            len == 0 || len > MAX_FRAGMENT_LEN
+        }
+        // Already over 16384 from the high byte.
+        (Some(a), None) => usize::from(*a) << 8 > MAX_FRAGMENT_LEN,
+        _ => false,
+    }
+}
@@ -0,0 +1,246 @@
+//! Edits of a parsed ClientHello, for proxies forwarding it upstream.
+//!
+//! Edits keep every extension well-formed, lengths are recomputed and checked when the
+//! ClientHello is encoded again with `ClientHelloPayload::to_record_bytes`.
+//!
+//! The binders of pre_shared_key are MACs over the ClientHello, keyed with the PSK, so an edit
+//! can't keep them valid. Every edit removes pre_shared_key along with early_data, except removals
+//! that find nothing to remove: the server does a full handshake instead of resuming, and any
+//! 0-RTT records the client sent after the ClientHello have to be dropped rather than forwarded.
+
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    codec::{PayloadU16, PayloadU8},
+    enums::{ContentType, ExtensionType, ProtocolVersion, ServerNameType},
+    handshake::{ClientExtension, ClientHelloPayload, ServerName},
+    message::{encode_client_hello_message, MAX_HANDSHAKE_SIZE},
+    record::encode_records,
+    server_name::normalize_host_name,
+    ServerNameError,
+};
+
+impl ClientHelloPayload {
+    /// Replaces the server_name extension with a single `host_name`, lowercased.
+    ///
+    /// The extension keeps its position, or goes first when there was none.
+    pub fn set_server_name(&mut self, host_name: &str) -> Result<(), RewriteError> {
+        let name = normalize_host_name(host_name.as_bytes())?;
+        let ext = ClientExtension::ServerName(vec![ServerName {
+            typ: ServerNameType::HostName,
+            payload: PayloadU16(name.into_bytes()),
+        }]);
+        self.remove_pre_shared_key();
+        self.replace_extension(ext, 0);
+        Ok(())
+    }
+
+    pub fn remove_server_name(&mut self) -> bool {
+        self.remove_extension(ExtensionType::ServerName)
+    }
+
+    /// Replaces the ALPN protocols, in the given order. No protocols removes the extension.
+    pub fn set_alpn<'p>(
+        &mut self,
+        protocols: impl IntoIterator<Item = &'p [u8]>,
+    ) -> Result<(), RewriteError> {
+        let protocols = protocols
+            .into_iter()
+            .map(|x| match x.len() {
+                1..=255 => Ok(PayloadU8(x.to_vec())),
+                _ => Err(RewriteError::InvalidAlpnProtocol),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if protocols.is_empty() {
+            self.remove_extension(ExtensionType::ALProtocolNegotiation);
+        } else {
+            self.remove_pre_shared_key();
+            let index = self.extensions.len();
+            self.replace_extension(ClientExtension::Protocols(protocols), index);
+        }
+        Ok(())
+    }
+
+    /// Keeps the ALPN protocols `f` returns `true` for. The extension is removed once empty.
+    pub fn retain_alpn(&mut self, mut f: impl FnMut(&[u8]) -> bool) {
+        let mut removed = false;
+        for ext in self.extensions.iter_mut() {
+            if let ClientExtension::Protocols(protocols) = ext {
+                let len = protocols.len();
+                protocols.retain(|x| f(&x.0));
+                removed |= protocols.len() != len;
+            }
+        }
+        if removed {
+            self.remove_pre_shared_key();
+            self.extensions
+                .retain(|x| !matches!(x, ClientExtension::Protocols(x) if x.is_empty()));
+        }
+    }
+
+    /// Removes every extension of type `typ`, returns whether there was one.
+    pub fn remove_extension(&mut self, typ: ExtensionType) -> bool {
+        let len = self.extensions.len();
+        self.extensions.retain(|x| x.get_type() != typ);
+        let removed = self.extensions.len() != len;
+        if removed {
+            self.remove_pre_shared_key();
+        }
+        removed
+    }
+
+    /// Encodes the ClientHello, fragmented across `records` records like
+    /// `record::encode_records`.
+    pub fn to_record_bytes(
+        &self,
+        record_version: ProtocolVersion,
+        records: usize,
+    ) -> Result<Vec<u8>, RewriteError> {
+        Ok(encode_records(
+            ContentType::Handshake,
+            record_version,
+            &self.to_handshake_bytes()?,
+            records,
+        ))
+    }
+
+    /// Encodes the ClientHello handshake message, header included.
+    pub fn to_handshake_bytes(&self) -> Result<Vec<u8>, RewriteError> {
+        check_lengths(self)?;
+        Ok(encode_client_hello_message(self))
+    }
+
+    // The binders can't be recomputed without the PSK, and early_data needs pre_shared_key.
+    pub(crate) fn remove_pre_shared_key(&mut self) {
+        self.extensions.retain(|x| {
+            !matches!(
+                x.get_type(),
+                ExtensionType::PreSharedKey | ExtensionType::EarlyData
+            )
+        });
+    }
+
+    // Replaces the first extension of the same type and removes the others, or inserts `ext` at
+    // `index`.
+    fn replace_extension(&mut self, ext: ClientExtension, index: usize) {
+        let typ = ext.get_type();
+        match self.extensions.iter().position(|x| x.get_type() == typ) {
+            Some(i) => {
+                self.extensions[i] = ext;
+                let mut n = 0;
+                self.extensions.retain(|x| {
+                    n += 1;
+                    n - 1 == i || x.get_type() != typ
+                });
+            }
+            None => self.extensions.insert(index, ext),
+        }
+    }
+}
+
+// Checked before encoding, the codec would write truncated length prefixes.
+fn check_lengths(chp: &ClientHelloPayload) -> Result<(), RewriteError> {
+    fn check(field: &'static str, len: usize, max: usize) -> Result<usize, RewriteError> {
+        if len > max {
+            return Err(RewriteError::FieldTooLarge { field, len });
+        }
+        Ok(len)
+    }
+    // Items with their own length prefix, inside a list with a u16 length prefix.
+    fn list_u16<'a>(
+        field: &'static str,
+        item: &'static str,
+        item_max: usize,
+        items: impl Iterator<Item = (usize, &'a [u8])>,
+    ) -> Result<usize, RewriteError> {
+        let mut len = 0;
+        for (header_len, x) in items {
+            len += header_len + check(item, x.len(), item_max)?;
+        }
+        Ok(2 + check(field, len, 0xffff)?)
+    }
+
+    check("cipher_suites", 2 * chp.cipher_suites.len(), 0xffff)?;
+    check("compression_methods", chp.compression_methods.len(), 0xff)?;
+
+    let mut extensions_len = 0;
+    for ext in chp.extensions.iter() {
+        let len = match ext {
+            ClientExtension::ECPointFormats(x) => 1 + check("ECPointFormats", x.len(), 0xff)?,
+            ClientExtension::NamedGroups(x) => 2 + check("NamedGroups", 2 * x.len(), 0xffff)?,
+            ClientExtension::ServerName(x) => list_u16(
+                "ServerName",
+                "HostName",
+                0xffff,
+                x.iter().map(|x| (1 + 2, x.payload.0.as_slice())),
+            )?,
+            ClientExtension::SignatureAlgorithms(x) => {
+                2 + check("SignatureAlgorithms", 2 * x.len(), 0xffff)?
+            }
+            ClientExtension::Protocols(x) => list_u16(
+                "ALProtocolNegotiation",
+                "ProtocolName",
+                0xff,
+                x.iter().map(|x| (1, x.0.as_slice())),
+            )?,
+            ClientExtension::SupportedVersions(x) => {
+                1 + check("SupportedVersions", 2 * x.len(), 0xff)?
+            }
+            ClientExtension::KeyShare(x) => list_u16(
+                "KeyShare",
+                "KeyExchange",
+                0xffff,
+                x.iter().map(|x| (2 + 2, x.payload.0.as_slice())),
+            )?,
+            ClientExtension::PresharedKeyModes(x) => {
+                1 + check("PSKKeyExchangeModes", x.len(), 0xff)?
+            }
+            ClientExtension::Unknown(x) => x.payload.len(),
+        };
+        if len > 0xffff {
+            return Err(RewriteError::MessageTooLarge(len));
+        }
+        extensions_len += 2 + 2 + len;
+    }
+    if extensions_len > MAX_HANDSHAKE_SIZE {
+        return Err(RewriteError::MessageTooLarge(extensions_len));
+    }
+
+    let mut len = 2 + 32 + 1 + chp.session_id.len();
+    len += 2 + 2 * chp.cipher_suites.len() + 1 + chp.compression_methods.len();
+    if !chp.extensions.is_empty() || chp.empty_extensions_block {
+        len += 2 + extensions_len;
+    }
+    if len > MAX_HANDSHAKE_SIZE {
+        return Err(RewriteError::MessageTooLarge(len));
+    }
+    Ok(())
+}
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RewriteError {
+    InvalidServerName(ServerNameError),
+    /// ALPN protocols are 1 to 255 bytes long.
+    InvalidAlpnProtocol,
+    MessageTooLarge(usize),
+    /// A length prefixed field nested in the ClientHello, named like the codec names it.
+    FieldTooLarge {
+        field: &'static str,
+        len: usize,
+    },
+}
+impl core::fmt::Display for RewriteError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for RewriteError {}
+
+impl From<ServerNameError> for RewriteError {
+    fn from(err: ServerNameError) -> Self {
+        Self::InvalidServerName(err)
+    }
+}
@@ -0,0 +1,283 @@
+//! The serde representation of a ClientHello, behind the `serde` feature.
+//!
+//! `ClientHelloPayload` serializes as a `ClientHelloDocument`:
+//!
+//! ```json
+//! {
+//!   "schema_version": 1,
+//!   "client_version": { "value": 771, "name": "TLSv1_2" },
+//!   "random": "<64 hex digits>",
+//!   "session_id": "<0 to 64 hex digits>",
+//!   "cipher_suites": [{ "value": 4865, "name": "TLS_AES_128_GCM_SHA256" }],
+//!   "compression_methods": [0],
+//!   "extensions": [{ "type": { "value": 0, "name": "server_name" }, "data": "000d00000a..." }],
+//!   "empty_extensions_block": false,
+//!   "server_name": "example.com",
+//!   "alpn": ["h2", "http/1.1"],
+//!   "ja3": "771,4865-...",
+//!   "ja3_hash": "<32 hex digits>",
+//!   "ja4": "t13d...",
+//!   "ja4_r": "t13d..."
+//! }
+//! ```
+//!
+//! - `value` is the wire value. `name` is informational: the IANA registry name, `"GREASE"` for
+//!   GREASE values and `null` for unassigned code points. Protocol versions have no registry and
+//!   are named like `ProtocolVersion`.
+//! - Extension `data` is the extension body as sent, so a document deserializes back to the
+//!   exact same ClientHello.
+//! - `server_name`, `alpn` and the fingerprints are derived from the rest and ignored when
+//!   deserializing. ALPN protocols that aren't UTF-8 are lossily converted. Fingerprints are
+//!   computed for TCP.
+//!
+//! `ClientHello` only borrows part of a ClientHello and serializes as the smaller
+//! `ClientHelloViewDocument`, with the same field names.
+//!
+//! Fields are only ever added, `schema_version` is bumped on any other change.
+
+use alloc::{
+    format,
+    string::{String, ToString as _},
+    vec::Vec,
+};
+use core::fmt::Write as _;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    enums::{CipherSuite, ExtensionType, ProtocolVersion},
+    handshake::{ClientExtension, ClientHelloPayload, Random, SessionId},
+    ja4::Transport,
+    ClientHello,
+};
+
+//
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientHelloDocument {
+    pub schema_version: u32,
+    pub client_version: NamedValue,
+    pub random: String,
+    pub session_id: String,
+    pub cipher_suites: Vec<NamedValue>,
+    pub compression_methods: Vec<u8>,
+    pub extensions: Vec<ExtensionDocument>,
+    #[serde(default)]
+    pub empty_extensions_block: bool,
+    #[serde(default)]
+    pub server_name: Option<String>,
+    #[serde(default)]
+    pub alpn: Option<Vec<String>>,
+    #[serde(default)]
+    pub ja3: String,
+    #[serde(default)]
+    pub ja3_hash: String,
+    #[serde(default)]
+    pub ja4: String,
+    #[serde(default)]
+    pub ja4_r: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientHelloViewDocument {
+    pub schema_version: u32,
+    pub client_version: NamedValue,
+    pub cipher_suites: Vec<NamedValue>,
+    pub extensions: Vec<ExtensionDocument>,
+    #[serde(default)]
+    pub server_name: Option<String>,
+    #[serde(default)]
+    pub alpn: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamedValue {
+    pub value: u16,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtensionDocument {
+    #[serde(rename = "type")]
+    pub typ: NamedValue,
+    pub data: String,
+}
+
+fn named(value: u16, name: Option<&str>, is_grease: bool) -> NamedValue {
+    let name = match is_grease {
+        true => Some("GREASE"),
+        false => name,
+    };
+    NamedValue {
+        value,
+        name: name.map(|x| x.to_string()),
+    }
+}
+
+fn version_named(x: ProtocolVersion) -> NamedValue {
+    let name = format!("{x:?}");
+    let name = Some(name.as_str()).filter(|x| !x.starts_with("Unknown("));
+    named(x.into(), name, x.is_grease())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        let _ = write!(s, "{b:02x}");
+    }
+    s
+}
+
+fn from_hex(s: &str) -> Result<Vec<u8>, DocumentError> {
+    if !s.len().is_multiple_of(2) {
+        return Err(DocumentError::InvalidHex);
+    }
+    s.as_bytes()
+        .chunks(2)
+        .map(|x| {
+            core::str::from_utf8(x)
+                .ok()
+                .and_then(|x| u8::from_str_radix(x, 16).ok())
+                .ok_or(DocumentError::InvalidHex)
+        })
+        .collect()
+}
+
+fn alpn(client_hello: &ClientHello<'_>) -> Option<Vec<String>> {
+    client_hello
+        .alpn()
+        .map(|x| x.map(|x| String::from_utf8_lossy(x).into_owned()).collect())
+}
+
+impl From<&ClientHelloPayload> for ClientHelloDocument {
+    fn from(chp: &ClientHelloPayload) -> Self {
+        let view = ClientHelloViewDocument::from(&chp.client_hello());
+
+        Self {
+            schema_version: SCHEMA_VERSION,
+            client_version: view.client_version,
+            random: to_hex(&chp.random.0),
+            session_id: to_hex(chp.session_id.as_ref()),
+            cipher_suites: view.cipher_suites,
+            compression_methods: chp
+                .compression_methods
+                .iter()
+                .map(|x| u8::from(*x))
+                .collect(),
+            extensions: view.extensions,
+            empty_extensions_block: chp.empty_extensions_block,
+            server_name: view.server_name,
+            alpn: view.alpn,
+            ja3: chp.ja3_string(),
+            ja3_hash: chp.ja3_hash(),
+            ja4: chp.ja4(Transport::Tcp),
+            ja4_r: chp.ja4_r(Transport::Tcp),
+        }
+    }
+}
+
+impl From<&ClientHello<'_>> for ClientHelloViewDocument {
+    fn from(client_hello: &ClientHello<'_>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            client_version: version_named(client_hello.client_version()),
+            cipher_suites: client_hello
+                .cipher_suites()
+                .iter()
+                .map(|x| named((*x).into(), x.iana().map(|x| x.name), x.is_grease()))
+                .collect(),
+            extensions: client_hello
+                .extensions()
+                .map(|(typ, data)| ExtensionDocument {
+                    typ: named(typ.into(), typ.iana().map(|x| x.name), typ.is_grease()),
+                    data: to_hex(&data),
+                })
+                .collect(),
+            server_name: client_hello.server_name().map(|x| x.to_string()),
+            alpn: alpn(client_hello),
+        }
+    }
+}
+
+impl TryFrom<ClientHelloDocument> for ClientHelloPayload {
+    type Error = DocumentError;
+
+    fn try_from(doc: ClientHelloDocument) -> Result<Self, Self::Error> {
+        if doc.schema_version != SCHEMA_VERSION {
+            return Err(DocumentError::UnsupportedSchemaVersion(doc.schema_version));
+        }
+
+        let random = from_hex(&doc.random)?
+            .try_into()
+            .map_err(|_| DocumentError::InvalidRandom)?;
+        let session_id =
+            SessionId::new(&from_hex(&doc.session_id)?).ok_or(DocumentError::InvalidSessionId)?;
+        let extensions = doc
+            .extensions
+            .iter()
+            .map(|x| {
+                let typ = ExtensionType::from(x.typ.value);
+                ClientExtension::read_payload(typ, &from_hex(&x.data)?)
+                    .map_err(|_| DocumentError::InvalidExtension(typ))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            client_version: ProtocolVersion::from(doc.client_version.value),
+            random: Random(random),
+            session_id,
+            cipher_suites: doc
+                .cipher_suites
+                .iter()
+                .map(|x| CipherSuite::from(x.value))
+                .collect(),
+            compression_methods: doc
+                .compression_methods
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            empty_extensions_block: doc.empty_extensions_block,
+            extensions,
+            wire_extensions: Vec::new(),
+        })
+    }
+}
+
+impl Serialize for ClientHelloPayload {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ClientHelloDocument::from(self).serialize(serializer)
+    }
+}
+
+impl Serialize for ClientHello<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ClientHelloViewDocument::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ClientHelloPayload {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ClientHelloDocument::deserialize(deserializer)?
+            .try_into()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentError {
+    UnsupportedSchemaVersion(u32),
+    InvalidHex,
+    InvalidRandom,
+    InvalidSessionId,
+    InvalidExtension(ExtensionType),
+}
+impl core::fmt::Display for DocumentError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for DocumentError {}
@@ -0,0 +1,393 @@
+//! Security classification of cipher suites, from their IANA registry names.
+//!
+//! `TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256` is `ECDHE` key exchange, `RSA` authentication,
+//! `AES_128_GCM` protection and a `SHA256` PRF. TLS 1.3 suites like `TLS_AES_128_GCM_SHA256` only
+//! name the protection, key exchange and authentication are negotiated by extensions.
+
+use core::iter::Peekable;
+
+use crate::{client_hello::ClientHello, enums::CipherSuite};
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyExchange {
+    Null,
+    Rsa,
+    Dh,
+    Dhe,
+    Ecdh,
+    Ecdhe,
+    Psk,
+    DhePsk,
+    RsaPsk,
+    EcdhePsk,
+    Krb5,
+    Srp,
+    EccPwd,
+    /// TLS 1.3, negotiated with the key_share and pre_shared_key extensions.
+    Any,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Authentication {
+    /// Anonymous, or the NULL suite.
+    None,
+    Rsa,
+    Dss,
+    Ecdsa,
+    Psk,
+    Krb5,
+    Srp,
+    Password,
+    /// TLS 1.3, negotiated with the signature_algorithms and pre_shared_key extensions.
+    Any,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BulkCipher {
+    Null,
+    Rc2,
+    Rc4,
+    Des,
+    TripleDes,
+    Idea,
+    Seed,
+    Aes,
+    Camellia,
+    Aria,
+    ChaCha20,
+    Sm4,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mac {
+    Null,
+    Md5,
+    Sha1,
+    Sha256,
+    Sha384,
+    Sm3,
+    // AEAD
+    Gcm,
+    Ccm,
+    Ccm8,
+    Poly1305,
+}
+
+/// Worst to best, `Ord` follows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Grade {
+    /// No encryption, no authentication, export grade or broken ciphers.
+    Insecure,
+    /// No forward secrecy, CBC mode or 64-bit block ciphers.
+    Weak,
+    /// AEAD with forward secrecy.
+    Secure,
+}
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CipherSuiteInfo {
+    pub key_exchange: KeyExchange,
+    pub authentication: Authentication,
+    pub cipher: BulkCipher,
+    /// 40 for export suites.
+    pub key_bits: u16,
+    pub mac: Mac,
+    pub export: bool,
+}
+
+/// What `CipherSuite::classify` makes of a registered suite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Classification {
+    Classified(CipherSuiteInfo),
+    /// The name doesn't decompose into a `CipherSuiteInfo`, like the GOST and AEGIS suites.
+    Unclassified,
+}
+
+impl Classification {
+    pub fn info(&self) -> Option<CipherSuiteInfo> {
+        match self {
+            Self::Classified(x) => Some(*x),
+            Self::Unclassified => None,
+        }
+    }
+}
+
+impl CipherSuiteInfo {
+    /// Classifies a suite from its IANA name, `None` for signaling values and names that don't
+    /// follow the usual pattern.
+    pub fn from_iana_name(name: &str) -> Option<Self> {
+        let name = name.strip_prefix("TLS_")?;
+
+        let (key_exchange, authentication, export, protection) = match name.split_once("_WITH_") {
+            Some((kx, protection)) => {
+                let (kx, export) = match kx.strip_suffix("_EXPORT") {
+                    Some(kx) => (kx, true),
+                    None => (kx, false),
+                };
+                let (key_exchange, authentication) = key_exchange(kx)?;
+                (key_exchange, authentication, export, protection)
+            }
+            None => (KeyExchange::Any, Authentication::Any, false, name),
+        };
+
+        let mut tokens = protection.split('_').peekable();
+        let (cipher, key_bits, aead) = match tokens.next()? {
+            "NULL" => (BulkCipher::Null, 0, None),
+            // TLS 1.3 integrity only, RFC 9150.
+            "SHA256" | "SHA384" if key_exchange == KeyExchange::Any => (BulkCipher::Null, 0, None),
+            "RC4" => (BulkCipher::Rc4, tokens.next()?.parse().ok()?, None),
+            "RC2" => {
+                expect(&mut tokens, "CBC")?;
+                (BulkCipher::Rc2, tokens.next()?.parse().ok()?, None)
+            }
+            "DES40" => {
+                expect(&mut tokens, "CBC")?;
+                (BulkCipher::Des, 40, None)
+            }
+            "DES" => {
+                expect(&mut tokens, "CBC")?;
+                match tokens.next_if_eq(&"40") {
+                    Some(_) => (BulkCipher::Des, 40, None),
+                    None => (BulkCipher::Des, 56, None),
+                }
+            }
+            "3DES" => {
+                expect(&mut tokens, "EDE")?;
+                expect(&mut tokens, "CBC")?;
+                (BulkCipher::TripleDes, 168, None)
+            }
+            "IDEA" => {
+                expect(&mut tokens, "CBC")?;
+                (BulkCipher::Idea, 128, None)
+            }
+            "SEED" => {
+                expect(&mut tokens, "CBC")?;
+                (BulkCipher::Seed, 128, None)
+            }
+            "CHACHA20" => {
+                expect(&mut tokens, "POLY1305")?;
+                (BulkCipher::ChaCha20, 256, Some(Mac::Poly1305))
+            }
+            "SM4" => (BulkCipher::Sm4, 128, aead_mode(&mut tokens)?),
+            cipher @ ("AES" | "CAMELLIA" | "ARIA") => {
+                let cipher = match cipher {
+                    "AES" => BulkCipher::Aes,
+                    "CAMELLIA" => BulkCipher::Camellia,
+                    _ => BulkCipher::Aria,
+                };
+                let key_bits = tokens.next()?.parse().ok()?;
+                (cipher, key_bits, aead_mode(&mut tokens)?)
+            }
+            _ => return None,
+        };
+
+        // The hash of AEAD suites is the PRF one.
+        let mac = match (aead, tokens.next()) {
+            (Some(mac), _) => mac,
+            (None, Some("NULL")) => Mac::Null,
+            (None, Some("MD5")) => Mac::Md5,
+            (None, Some("SHA")) => Mac::Sha1,
+            (None, Some("SHA256")) => Mac::Sha256,
+            (None, Some("SHA384")) => Mac::Sha384,
+            (None, Some("SM3")) => Mac::Sm3,
+            _ => return None,
+        };
+
+        Some(Self {
+            key_exchange,
+            authentication,
+            cipher,
+            key_bits,
+            mac,
+            export,
+        })
+    }
+
+    pub fn is_aead(&self) -> bool {
+        matches!(self.mac, Mac::Gcm | Mac::Ccm | Mac::Ccm8 | Mac::Poly1305)
+    }
+
+    /// A block cipher in CBC mode.
+    pub fn is_cbc(&self) -> bool {
+        !self.is_aead() && !matches!(self.cipher, BulkCipher::Null | BulkCipher::Rc4)
+    }
+
+    pub fn is_anonymous(&self) -> bool {
+        self.authentication == Authentication::None && self.key_exchange != KeyExchange::Null
+    }
+
+    pub fn forward_secrecy(&self) -> bool {
+        matches!(
+            self.key_exchange,
+            KeyExchange::Dhe
+                | KeyExchange::Ecdhe
+                | KeyExchange::DhePsk
+                | KeyExchange::EcdhePsk
+                | KeyExchange::Srp
+                | KeyExchange::EccPwd
+                | KeyExchange::Any
+        )
+    }
+
+    pub fn grade(&self) -> Grade {
+        if self.cipher == BulkCipher::Null
+            || self.authentication == Authentication::None
+            || self.export
+            || matches!(
+                self.cipher,
+                BulkCipher::Rc2 | BulkCipher::Rc4 | BulkCipher::Des
+            )
+        {
+            Grade::Insecure
+        } else if matches!(self.cipher, BulkCipher::TripleDes | BulkCipher::Idea)
+            || self.is_cbc()
+            || !self.forward_secrecy()
+        {
+            Grade::Weak
+        } else {
+            Grade::Secure
+        }
+    }
+}
+
+fn key_exchange(kx: &str) -> Option<(KeyExchange, Authentication)> {
+    Some(match kx {
+        "NULL" => (KeyExchange::Null, Authentication::None),
+        "RSA" => (KeyExchange::Rsa, Authentication::Rsa),
+        "DH_DSS" => (KeyExchange::Dh, Authentication::Dss),
+        "DH_RSA" => (KeyExchange::Dh, Authentication::Rsa),
+        "DHE_DSS" => (KeyExchange::Dhe, Authentication::Dss),
+        "DHE_RSA" => (KeyExchange::Dhe, Authentication::Rsa),
+        "DH_anon" => (KeyExchange::Dhe, Authentication::None),
+        "ECDH_ECDSA" => (KeyExchange::Ecdh, Authentication::Ecdsa),
+        "ECDH_RSA" => (KeyExchange::Ecdh, Authentication::Rsa),
+        "ECDHE_ECDSA" => (KeyExchange::Ecdhe, Authentication::Ecdsa),
+        "ECDHE_RSA" => (KeyExchange::Ecdhe, Authentication::Rsa),
+        "ECDH_anon" => (KeyExchange::Ecdhe, Authentication::None),
+        "PSK" => (KeyExchange::Psk, Authentication::Psk),
+        "DHE_PSK" | "PSK_DHE" => (KeyExchange::DhePsk, Authentication::Psk),
+        "RSA_PSK" => (KeyExchange::RsaPsk, Authentication::Psk),
+        "ECDHE_PSK" => (KeyExchange::EcdhePsk, Authentication::Psk),
+        "KRB5" => (KeyExchange::Krb5, Authentication::Krb5),
+        "SRP_SHA" => (KeyExchange::Srp, Authentication::Srp),
+        "SRP_SHA_RSA" => (KeyExchange::Srp, Authentication::Rsa),
+        "SRP_SHA_DSS" => (KeyExchange::Srp, Authentication::Dss),
+        "ECCPWD" => (KeyExchange::EccPwd, Authentication::Password),
+        _ => return None,
+    })
+}
+
+fn expect<'a>(tokens: &mut impl Iterator<Item = &'a str>, token: &str) -> Option<()> {
+    (tokens.next()? == token).then_some(())
+}
+
+// `None` for CBC, which has a separate MAC.
+fn aead_mode<'a, I: Iterator<Item = &'a str>>(tokens: &mut Peekable<I>) -> Option<Option<Mac>> {
+    Some(match tokens.next()? {
+        "CBC" => None,
+        "GCM" => Some(Mac::Gcm),
+        "CCM" => match tokens.next_if_eq(&"8") {
+            Some(_) => Some(Mac::Ccm8),
+            None => Some(Mac::Ccm),
+        },
+        _ => return None,
+    })
+}
+
+impl CipherSuite {
+    /// `None` for GREASE, signaling values, reserved code points and suites missing from `iana`.
+    pub fn classify(&self) -> Option<Classification> {
+        if self.is_grease() || self.is_signaling() {
+            return None;
+        }
+        let name = self.iana()?.name;
+        if !name.starts_with("TLS_") {
+            return None;
+        }
+        Some(match CipherSuiteInfo::from_iana_name(name) {
+            Some(info) => Classification::Classified(info),
+            None => Classification::Unclassified,
+        })
+    }
+
+    /// TLS_EMPTY_RENEGOTIATION_INFO_SCSV and TLS_FALLBACK_SCSV.
+    pub fn is_signaling(&self) -> bool {
+        self.iana().is_some_and(|x| x.name.ends_with("_SCSV"))
+    }
+}
+
+//
+/// Counts of the offered cipher suites, GREASE and signaling values left out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CipherSuiteSummary {
+    pub suites: usize,
+    /// Not in `iana`.
+    pub unknown: usize,
+    /// Registered, but `Classification::Unclassified`. Only counted here and in `suites`.
+    pub unclassified: usize,
+    pub null: usize,
+    pub anonymous: usize,
+    pub export: usize,
+    pub rc4: usize,
+    pub triple_des: usize,
+    pub cbc: usize,
+    pub aead: usize,
+    pub non_forward_secret: usize,
+    /// Of the classified suites.
+    pub worst_grade: Option<Grade>,
+    pub best_grade: Option<Grade>,
+}
+
+impl CipherSuiteSummary {
+    /// CBC suites are offered, AEAD ones aren't.
+    pub fn cbc_only(&self) -> bool {
+        self.cbc > 0 && self.aead == 0
+    }
+}
+
+impl FromIterator<CipherSuite> for CipherSuiteSummary {
+    fn from_iter<I: IntoIterator<Item = CipherSuite>>(iter: I) -> Self {
+        let mut summary = Self::default();
+
+        for cipher_suite in iter {
+            if cipher_suite.is_grease() || cipher_suite.is_signaling() {
+                continue;
+            }
+            summary.suites += 1;
+
+            let info = match cipher_suite.classify() {
+                Some(Classification::Classified(info)) => info,
+                Some(Classification::Unclassified) => {
+                    summary.unclassified += 1;
+                    continue;
+                }
+                None => {
+                    summary.unknown += 1;
+                    continue;
+                }
+            };
+            summary.null += usize::from(info.cipher == BulkCipher::Null);
+            summary.anonymous += usize::from(info.is_anonymous());
+            summary.export += usize::from(info.export);
+            summary.rc4 += usize::from(info.cipher == BulkCipher::Rc4);
+            summary.triple_des += usize::from(info.cipher == BulkCipher::TripleDes);
+            summary.cbc += usize::from(info.is_cbc());
+            summary.aead += usize::from(info.is_aead());
+            summary.non_forward_secret += usize::from(!info.forward_secrecy());
+
+            let grade = info.grade();
+            summary.worst_grade = Some(summary.worst_grade.map_or(grade, |x| x.min(grade)));
+            summary.best_grade = Some(summary.best_grade.map_or(grade, |x| x.max(grade)));
+        }
+
+        summary
+    }
+}
+
+impl ClientHello<'_> {
+    pub fn cipher_suite_summary(&self) -> CipherSuiteSummary {
+        self.cipher_suites().iter().copied().collect()
+    }
+}
@@ -0,0 +1,109 @@
+// https://www.rfc-editor.org/rfc/rfc6066.html#section-3
+// https://www.rfc-editor.org/rfc/rfc1123.html#section-2
+
+use alloc::string::String;
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+//
+pub const MAX_HOST_NAME_LEN: usize = 253;
+pub const MAX_LABEL_LEN: usize = 63;
+
+/// Lowercases `name`, strips one trailing dot and checks it is a valid LDH host name.
+///
+/// Underscores are accepted like webpki does, they are common in names of internal services.
+pub fn normalize_host_name(name: &[u8]) -> Result<String, ServerNameError> {
+    let name = name.strip_suffix(b".").unwrap_or(name);
+    if name.is_empty() {
+        return Err(ServerNameError::Empty);
+    }
+
+    if let Ok(s) = core::str::from_utf8(name) {
+        let s = s
+            .strip_prefix('[')
+            .and_then(|x| x.strip_suffix(']'))
+            .unwrap_or(s);
+        if s.parse::<Ipv4Addr>().is_ok() || s.parse::<Ipv6Addr>().is_ok() {
+            return Err(ServerNameError::IpAddress);
+        }
+    }
+
+    if name.len() > MAX_HOST_NAME_LEN {
+        return Err(ServerNameError::TooLong(name.len()));
+    }
+
+    for label in name.split(|x| *x == b'.') {
+        if label.is_empty() {
+            return Err(ServerNameError::EmptyLabel);
+        }
+        if label.len() > MAX_LABEL_LEN {
+            return Err(ServerNameError::LabelTooLong(label.len()));
+        }
+        if let Some(x) = label
+            .iter()
+            .find(|x| !(x.is_ascii_alphanumeric() || **x == b'-' || **x == b'_'))
+        {
+            return Err(ServerNameError::InvalidCharacter(*x));
+        }
+        if label.starts_with(b"-") || label.ends_with(b"-") {
+            return Err(ServerNameError::HyphenAtLabelEdge);
+        }
+    }
+
+    // The top-level label of a host name is never all-numeric.
+    if name
+        .rsplit(|x| *x == b'.')
+        .next()
+        .is_some_and(|x| x.iter().all(u8::is_ascii_digit))
+    {
+        return Err(ServerNameError::NumericTopLevelLabel);
+    }
+
+    Ok(name
+        .iter()
+        .map(|x| char::from(x.to_ascii_lowercase()))
+        .collect())
+}
+
+/// Maps `name` with UTS #46 and returns its lowercase A-label form, validated like
+/// `normalize_host_name`.
+///
+/// U-labels, uppercase A-labels and full-width characters all end up as the same name.
+#[cfg(feature = "idna")]
+pub fn normalize_idn_host_name(name: &[u8]) -> Result<String, ServerNameError> {
+    let ascii = idna::domain_to_ascii_cow(name, idna::AsciiDenyList::URL)
+        .map_err(|_| ServerNameError::InvalidIdn)?;
+    normalize_host_name(ascii.as_bytes())
+}
+
+/// The Unicode form of `normalize_idn_host_name`, A-labels are decoded to U-labels.
+#[cfg(feature = "idna")]
+pub fn idn_host_name_to_unicode(name: &[u8]) -> Result<String, ServerNameError> {
+    let ascii = normalize_idn_host_name(name)?;
+    match idna::domain_to_unicode(&ascii) {
+        (unicode, Ok(())) => Ok(unicode),
+        (_, Err(_)) => Err(ServerNameError::InvalidIdn),
+    }
+}
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerNameError {
+    MultipleHostNames,
+    Empty,
+    IpAddress,
+    TooLong(usize),
+    EmptyLabel,
+    LabelTooLong(usize),
+    InvalidCharacter(u8),
+    HyphenAtLabelEdge,
+    NumericTopLevelLabel,
+    /// Only returned by the `idna` feature functions.
+    InvalidIdn,
+}
+impl core::fmt::Display for ServerNameError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for ServerNameError {}
@@ -0,0 +1,137 @@
+#![cfg(all(feature = "std", feature = "arbitrary"))]
+
+use arbitrary::{Arbitrary as _, Unstructured};
+use tls_client_hello_parser::{
+    codec::Codec as _,
+    enums::{ContentType, ProtocolVersion},
+    ja4::Transport,
+    record::encode_records,
+    ClientHelloPayload, ClientHelloRef, Parser, ParserConfig, Progress,
+};
+
+const ITERATIONS: usize = 2000;
+
+// xorshift64, keeps the cases reproducible without a rand dependency.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn bytes(&mut self, len: usize) -> Vec<u8> {
+        (0..len).map(|_| self.next() as u8).collect()
+    }
+}
+
+fn exercise(chp: &ClientHelloPayload) {
+    let client_hello = chp.client_hello();
+    let _ = client_hello.server_name();
+    let _ = client_hello.server_names().map(|x| x.count());
+    let _ = client_hello.normalized_server_name();
+    let _ = client_hello.alpn().map(|x| x.count());
+    let _ = client_hello.extensions().count();
+    let _ = client_hello.duplicate_extensions();
+    let _ = client_hello.unknown_extensions().count();
+    let _ = client_hello.key_shares().map(|x| x.count());
+    let _ = client_hello.effective_max_version();
+    let _ = client_hello.validate_for_rustls();
+    let _ = chp.ja3_hash();
+    let _ = chp.ja4(Transport::Tcp);
+}
+
+#[test]
+fn test_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+
+    for _ in 0..ITERATIONS {
+        let data = rng.bytes(1024);
+        let mut u = Unstructured::new(&data);
+        let chp = ClientHelloPayload::arbitrary(&mut u)?;
+        let records = usize::from(u8::arbitrary(&mut u)?);
+
+        let handshake = chp.to_handshake_bytes()?;
+        let body = &handshake[4..];
+        let buf = encode_records(
+            ContentType::Handshake,
+            ProtocolVersion::TLSv1_0,
+            &handshake,
+            records,
+        );
+
+        let config = ParserConfig {
+            max_records: usize::MAX,
+            ..Default::default()
+        };
+        match Parser::with_config(config).feed(&buf)? {
+            Progress::Done(parsed, len) => {
+                assert_eq!(parsed, chp);
+                assert_eq!(len, buf.len());
+                assert_eq!(parsed.to_handshake_bytes()?, handshake);
+                exercise(&parsed);
+            }
+            x => panic!("{x:?}"),
+        }
+
+        assert_eq!(ClientHelloPayload::read_bytes(body)?, chp);
+        assert_eq!(ClientHelloRef::read_bytes(body)?.to_owned()?, chp);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_no_panic() -> Result<(), Box<dyn std::error::Error>> {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+    for i in 0..ITERATIONS {
+        // Random bytes rarely get past the record header, corrupt valid ClientHellos too.
+        let buf = if i % 2 == 0 {
+            let len = (rng.next() % 512) as usize;
+            rng.bytes(len)
+        } else {
+            let data = rng.bytes(1024);
+            let chp = ClientHelloPayload::arbitrary(&mut Unstructured::new(&data))?;
+            let mut buf = encode_records(
+                ContentType::Handshake,
+                ProtocolVersion::TLSv1_0,
+                &chp.to_handshake_bytes()?,
+                (rng.next() % 300) as usize,
+            );
+            for _ in 0..1 + rng.next() % 4 {
+                let i = (rng.next() as usize) % buf.len();
+                buf[i] = rng.next() as u8;
+            }
+            buf
+        };
+
+        if let Ok(Progress::Done(chp, _)) = Parser::new().feed(&buf) {
+            exercise(&chp);
+        }
+        let mut parser = Parser::new();
+        for chunk in buf.chunks(7) {
+            match parser.parse_bytes(chunk) {
+                Ok(None) => {}
+                Ok(Some(chp)) => {
+                    exercise(&chp);
+                    break;
+                }
+                Err(_) => break,
+            }
+        }
+        if let Ok(Some(chr)) = ClientHelloRef::parse(&buf) {
+            let _ = chr.sni_host_name();
+            for ext in chr.extensions() {
+                let _ = ext.decode();
+            }
+        }
+        if let Ok(chp) = ClientHelloPayload::read_bytes(buf.get(9..).unwrap_or_default()) {
+            exercise(&chp);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,228 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use tls_client_hello_parser::{
+    codec::{Codec as _, PayloadU16, PayloadU8},
+    enums::{
+        CipherSuite, Compression, ContentType, ECPointFormat, ExtensionType, NamedGroup,
+        PSKKeyExchangeMode, ProtocolVersion, ServerNameType,
+    },
+    handshake::{ClientExtension, KeyShareEntry, ServerName, SessionId, UnknownExtension},
+    record::{encode_records, read_record},
+    ClientHelloBuilder, ClientHelloPayload, RewriteError,
+};
+
+use common::{parse, rustls_client_hello_record};
+
+fn record_lens(mut buf: &[u8]) -> Vec<usize> {
+    let mut lens = vec![];
+    while let Some((header, fragment)) = read_record(buf).expect("read_record") {
+        lens.push(fragment.len());
+        buf = &buf[5 + usize::from(header.len)..];
+    }
+    assert!(buf.is_empty());
+    lens
+}
+
+#[test]
+fn test_reencode_rustls() -> Result<(), Box<dyn std::error::Error>> {
+    let buf = rustls_client_hello_record()?;
+
+    let chp = parse(&buf);
+    assert_eq!(chp.to_handshake_bytes()?, &buf[5..]);
+
+    let builder = ClientHelloBuilder::from(chp)
+        .with_record_version(ProtocolVersion::from(u16::from_be_bytes([buf[1], buf[2]])));
+    assert_eq!(builder.to_handshake_bytes()?, &buf[5..]);
+    assert_eq!(builder.to_record_bytes()?, buf);
+
+    Ok(())
+}
+
+#[test]
+fn test_reencode_extensions_block() -> Result<(), Box<dyn std::error::Error>> {
+    let mut body = vec![0x03, 0x03];
+    body.extend_from_slice(&[0; 32]);
+    body.push(0);
+    body.extend_from_slice(&[0, 2, 0x13, 0x01]);
+    body.extend_from_slice(&[1, 0]);
+
+    // No extensions block.
+    let chp = ClientHelloPayload::read_bytes(&body)?;
+    assert!(!chp.empty_extensions_block);
+    assert_eq!(&chp.to_handshake_bytes()?[4..], body);
+
+    // An empty one.
+    body.extend_from_slice(&[0, 0]);
+    let chp = ClientHelloPayload::read_bytes(&body)?;
+    assert!(chp.empty_extensions_block);
+    assert_eq!(&chp.to_handshake_bytes()?[4..], body);
+
+    let builder = ClientHelloBuilder::from(chp);
+    assert_eq!(
+        &parse(&builder.to_record_bytes()?).to_handshake_bytes()?[4..],
+        body
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_builder() -> Result<(), Box<dyn std::error::Error>> {
+    let builder = ClientHelloBuilder::new()
+        .with_client_version(ProtocolVersion::TLSv1_2)
+        .with_random([7; 32])
+        .with_session_id(SessionId::new(&[1; 32]).ok_or("SessionId::new")?)
+        .with_cipher_suites([
+            CipherSuite::TLS13_AES_128_GCM_SHA256,
+            CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256,
+        ])
+        .with_extension(ClientExtension::SupportedVersions(vec![
+            ProtocolVersion::TLSv1_3,
+            ProtocolVersion::TLSv1_2,
+        ]))
+        .with_extension(ClientExtension::Protocols(vec![PayloadU8(b"h2".to_vec())]))
+        .with_extension(ClientExtension::ServerName(vec![ServerName {
+            typ: ServerNameType::HostName,
+            payload: PayloadU16(b"example.com".to_vec()),
+        }]))
+        .with_extension(ClientExtension::Unknown(UnknownExtension {
+            typ: ExtensionType::Padding,
+            payload: vec![0; 100],
+        }))
+        .with_records(3);
+
+    let buf = builder.to_record_bytes()?;
+    assert_eq!(record_lens(&buf).len(), 3);
+    assert_eq!(buf[..3], [0x16, 0x03, 0x01]);
+
+    let chp = parse(&buf);
+    assert_eq!(&chp, builder.payload());
+    assert_eq!(
+        chp.extensions
+            .iter()
+            .map(|x| x.get_type())
+            .collect::<Vec<_>>(),
+        [
+            ExtensionType::SupportedVersions,
+            ExtensionType::ALProtocolNegotiation,
+            ExtensionType::ServerName,
+            ExtensionType::Padding
+        ]
+    );
+    assert_eq!(chp.client_hello().server_name(), Some("example.com"));
+    assert_eq!(chp.session_id.as_ref(), &[1; 32]);
+
+    let payload = builder.clone().build();
+    assert_eq!(
+        ClientHelloBuilder::from(payload)
+            .with_records(3)
+            .to_record_bytes()?,
+        buf
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_length_overflow() -> Result<(), Box<dyn std::error::Error>> {
+    let too_large = |builder: ClientHelloBuilder| match builder.to_record_bytes() {
+        Err(RewriteError::FieldTooLarge { field, len }) => {
+            assert!(builder.to_handshake_bytes().is_err());
+            (field, len)
+        }
+        x => panic!("{x:?}"),
+    };
+
+    assert_eq!(
+        too_large(ClientHelloBuilder::new().with_compression_methods(vec![Compression::Null; 256])),
+        ("compression_methods", 256)
+    );
+    assert_eq!(
+        too_large(
+            ClientHelloBuilder::new()
+                .with_cipher_suites(vec![CipherSuite::Unknown(0xff00); 0x8000])
+        ),
+        ("cipher_suites", 0x10000)
+    );
+
+    let too_large_extension = |ext| too_large(ClientHelloBuilder::new().with_extension(ext));
+    assert_eq!(
+        too_large_extension(ClientExtension::Protocols(vec![
+            PayloadU8(b"h2".to_vec()),
+            PayloadU8(vec![b'a'; 256]),
+        ])),
+        ("ProtocolName", 256)
+    );
+    assert_eq!(
+        too_large_extension(ClientExtension::SupportedVersions(vec![
+            ProtocolVersion::TLSv1_3;
+            128
+        ])),
+        ("SupportedVersions", 256)
+    );
+    assert_eq!(
+        too_large_extension(ClientExtension::ECPointFormats(vec![
+            ECPointFormat::Uncompressed;
+            256
+        ])),
+        ("ECPointFormats", 256)
+    );
+    assert_eq!(
+        too_large_extension(ClientExtension::PresharedKeyModes(vec![
+            PSKKeyExchangeMode::PSK_DHE_KE;
+            256
+        ])),
+        ("PSKKeyExchangeModes", 256)
+    );
+    assert_eq!(
+        too_large_extension(ClientExtension::KeyShare(vec![KeyShareEntry {
+            group: NamedGroup::X25519,
+            payload: PayloadU16(vec![0; 0x10000]),
+        }])),
+        ("KeyExchange", 0x10000)
+    );
+    assert_eq!(
+        too_large_extension(ClientExtension::ServerName(vec![
+            ServerName {
+                typ: ServerNameType::HostName,
+                payload: PayloadU16(vec![b'a'; 0x8000]),
+            };
+            2
+        ])),
+        ("ServerName", 2 * (3 + 0x8000))
+    );
+
+    // At the limits.
+    let builder = ClientHelloBuilder::new()
+        .with_compression_methods(vec![Compression::Null; 255])
+        .with_extension(ClientExtension::Protocols(vec![PayloadU8(vec![b'a'; 255])]))
+        .with_extension(ClientExtension::SupportedVersions(vec![
+            ProtocolVersion::TLSv1_3;
+            127
+        ]));
+    assert_eq!(&parse(&builder.to_record_bytes()?), builder.payload());
+
+    Ok(())
+}
+
+#[test]
+fn test_encode_records() {
+    let payload = vec![1; 40000];
+
+    let lens = |records| {
+        record_lens(&encode_records(
+            ContentType::Handshake,
+            ProtocolVersion::TLSv1_2,
+            &payload,
+            records,
+        ))
+    };
+    assert_eq!(lens(0), [13334, 13333, 13333]);
+    assert_eq!(lens(1), [13334, 13333, 13333]);
+    assert_eq!(lens(4), [10000; 4]);
+
+    let buf = encode_records(ContentType::Handshake, ProtocolVersion::TLSv1_2, &[1, 2], 5);
+    assert_eq!(buf, [0x16, 0x03, 0x03, 0, 1, 1, 0x16, 0x03, 0x03, 0, 1, 2]);
+}
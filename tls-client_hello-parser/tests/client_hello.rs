@@ -0,0 +1,334 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use std::borrow::Cow;
+
+use tls_client_hello_parser::{
+    codec::Codec as _,
+    enums::{
+        Compression, ExtensionType, NamedGroup, PSKKeyExchangeMode, ProtocolVersion,
+        SignatureScheme,
+    },
+    handshake::ClientExtension,
+    ClientHelloError, ClientHelloPayload,
+};
+
+use common::{client_hello_body, minimal_client_hello_body, server_name};
+
+#[test]
+fn test_extensions() -> Result<(), Box<dyn std::error::Error>> {
+    let extensions = [
+        (0x0a0a, vec![]),
+        (0x000d, vec![0, 4, 0x04, 0x03, 0x08, 0x07]),
+        (0x0017, vec![]),
+        (0x1234, vec![1, 2, 3]),
+        (0x0015, vec![0; 5]),
+        (0x0017, vec![]),
+        (0x000d, vec![0, 2, 0x04, 0x01]),
+        (0x0017, vec![]),
+    ];
+    let chp = ClientHelloPayload::read_bytes(&minimal_client_hello_body(&extensions))?;
+    let client_hello = chp.client_hello();
+
+    assert_eq!(
+        client_hello
+            .extensions()
+            .map(|(typ, payload)| (u16::from(typ), payload.into_owned()))
+            .collect::<Vec<_>>(),
+        extensions
+    );
+    assert!(client_hello
+        .extensions()
+        .all(|(_, payload)| matches!(payload, Cow::Borrowed(_))));
+
+    assert_eq!(client_hello.decoded_extensions().len(), extensions.len());
+    assert_eq!(
+        client_hello.find_extension(ExtensionType::SignatureAlgorithms),
+        Some(&ClientExtension::SignatureAlgorithms(vec![
+            SignatureScheme::ECDSA_NISTP256_SHA256,
+            SignatureScheme::ED25519
+        ]))
+    );
+    assert!(matches!(
+        client_hello.find_extension(ExtensionType::Padding),
+        Some(ClientExtension::Unknown(x)) if x.payload == [0; 5]
+    ));
+    assert_eq!(client_hello.find_extension(ExtensionType::ServerName), None);
+
+    assert_eq!(
+        client_hello.duplicate_extensions(),
+        [
+            ExtensionType::ExtendedMasterSecret,
+            ExtensionType::SignatureAlgorithms
+        ]
+    );
+    assert_eq!(
+        client_hello.unknown_extensions().collect::<Vec<_>>(),
+        [ExtensionType::Unknown(0x1234)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_extensions_edited() -> Result<(), Box<dyn std::error::Error>> {
+    let mut chp = ClientHelloPayload::read_bytes(&minimal_client_hello_body(&[
+        (0x000d, vec![0, 2, 0x04, 0x01]),
+        (0x000a, vec![0, 2, 0x00, 0x1d]),
+    ]))?;
+    chp.extensions[0] =
+        ClientExtension::SignatureAlgorithms(vec![SignatureScheme::RSA_PKCS1_SHA384]);
+    chp.extensions.push(ClientExtension::SupportedVersions(vec![
+        ProtocolVersion::TLSv1_3,
+    ]));
+
+    let extensions = chp.client_hello().extensions().collect::<Vec<_>>();
+    assert_eq!(
+        extensions,
+        [
+            (
+                ExtensionType::SignatureAlgorithms,
+                Cow::Owned(vec![0, 2, 0x05, 0x01])
+            ),
+            (
+                ExtensionType::EllipticCurves,
+                Cow::Borrowed(&[0, 2, 0x00, 0x1d][..])
+            ),
+            (
+                ExtensionType::SupportedVersions,
+                Cow::Owned(vec![2, 0x03, 0x04])
+            ),
+        ]
+    );
+    assert!(matches!(extensions[0].1, Cow::Owned(_)));
+    assert!(matches!(extensions[1].1, Cow::Borrowed(_)));
+    assert!(matches!(extensions[2].1, Cow::Owned(_)));
+
+    Ok(())
+}
+
+#[test]
+fn test_extensions_none() -> Result<(), Box<dyn std::error::Error>> {
+    let chp = ClientHelloPayload::read_bytes(&minimal_client_hello_body(&[(
+        0x000d,
+        vec![0, 2, 0x04, 0x01],
+    )]))?;
+    let client_hello = chp.client_hello();
+
+    assert_eq!(client_hello.extensions().count(), 1);
+    assert!(client_hello.duplicate_extensions().is_empty());
+    assert_eq!(client_hello.unknown_extensions().count(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_versions_groups_key_shares() -> Result<(), Box<dyn std::error::Error>> {
+    let mut entries = vec![0x2a, 0x2a, 0, 1, 0];
+    entries.extend_from_slice(&[0x11, 0xec, 0x04, 0xc0]);
+    entries.extend_from_slice(&[0; 1216]);
+    entries.extend_from_slice(&[0x00, 0x1d, 0, 32]);
+    entries.extend_from_slice(&[0; 32]);
+    let mut key_share = (entries.len() as u16).to_be_bytes().to_vec();
+    key_share.extend_from_slice(&entries);
+
+    let chp = ClientHelloPayload::read_bytes(&minimal_client_hello_body(&[
+        (0x000d, vec![0, 2, 0x04, 0x03]),
+        (0x002b, vec![6, 0x3a, 0x3a, 0x03, 0x04, 0x03, 0x03]),
+        (
+            0x000a,
+            vec![0, 8, 0x4a, 0x4a, 0x11, 0xec, 0x00, 0x1d, 0x00, 0x17],
+        ),
+        (0x0033, key_share),
+        (0x002d, vec![2, 0x2a, 0x01]),
+    ]))?;
+    let client_hello = chp.client_hello();
+
+    assert_eq!(client_hello.client_version(), ProtocolVersion::TLSv1_2);
+    assert_eq!(
+        client_hello.supported_versions(),
+        Some(
+            &[
+                ProtocolVersion::Unknown(0x3a3a),
+                ProtocolVersion::TLSv1_3,
+                ProtocolVersion::TLSv1_2
+            ][..]
+        )
+    );
+    assert_eq!(
+        client_hello.effective_max_version(),
+        ProtocolVersion::TLSv1_3
+    );
+    assert_eq!(
+        client_hello.supported_groups(),
+        Some(
+            &[
+                NamedGroup::Unknown(0x4a4a),
+                NamedGroup::X25519MLKEM768,
+                NamedGroup::X25519,
+                NamedGroup::secp256r1
+            ][..]
+        )
+    );
+    assert_eq!(
+        client_hello.key_shares().map(|x| x.collect::<Vec<_>>()),
+        Some(vec![
+            (NamedGroup::Unknown(0x2a2a), 1),
+            (NamedGroup::X25519MLKEM768, 1216),
+            (NamedGroup::X25519, 32)
+        ])
+    );
+    assert_eq!(
+        client_hello.psk_modes(),
+        Some(
+            &[
+                PSKKeyExchangeMode::Unknown(0x2a),
+                PSKKeyExchangeMode::PSK_DHE_KE
+            ][..]
+        )
+    );
+    assert!(client_hello.psk_modes().unwrap()[0].is_grease());
+
+    Ok(())
+}
+
+#[test]
+fn test_effective_max_version() -> Result<(), Box<dyn std::error::Error>> {
+    for (version, extensions, expected) in [
+        (0x0301, vec![], ProtocolVersion::TLSv1_0),
+        (0x0303, vec![], ProtocolVersion::TLSv1_2),
+        (
+            0x0303,
+            vec![(0x002b, vec![2, 0x0a, 0x0a])],
+            ProtocolVersion::TLSv1_2,
+        ),
+        (
+            0x0303,
+            vec![(0x002b, vec![4, 0x03, 0x03, 0x03, 0x04])],
+            ProtocolVersion::TLSv1_3,
+        ),
+        (
+            0xfefd,
+            vec![(0x002b, vec![4, 0xfe, 0xfd, 0xfe, 0xfc])],
+            ProtocolVersion::DTLSv1_3,
+        ),
+    ] {
+        let mut extensions = extensions;
+        extensions.push((0x000d, vec![0, 2, 0x04, 0x03]));
+        let chp = ClientHelloPayload::read_bytes(&client_hello_body(
+            version,
+            &[0x1301],
+            Some(&extensions),
+        ))?;
+        assert_eq!(chp.client_hello().effective_max_version(), expected);
+        assert_eq!(chp.client_hello().key_shares().map(|x| x.count()), None);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_for_rustls() -> Result<(), Box<dyn std::error::Error>> {
+    let sigalgs = (0x000d, vec![0, 2, 0x04, 0x03]);
+    let sni = |names: &[&[u8]]| {
+        let names = names.iter().map(|x| (0, *x)).collect::<Vec<_>>();
+        (0x0000, server_name(&names))
+    };
+
+    // A TLS 1.0 client without signature_algorithms can still be inspected.
+    let chp = ClientHelloPayload::read_bytes(&client_hello_body(
+        0x0301,
+        &[0x1301],
+        Some(&[sni(&[b"example.com"])]),
+    ))?;
+    let client_hello = chp.client_hello();
+    assert_eq!(client_hello.server_name(), Some("example.com"));
+    assert_eq!(client_hello.signature_schemes(), None);
+    assert_eq!(
+        client_hello.validate_for_rustls(),
+        Err(ClientHelloError::PeerIncompatible(
+            "client does not support TLSv1_2 or TLSv1_3"
+        ))
+    );
+
+    for (version, extensions, expected) in [
+        (0x0303, vec![sigalgs.clone()], Ok(())),
+        (
+            0x0301,
+            vec![sigalgs.clone(), (0x002b, vec![2, 0x03, 0x04])],
+            Ok(()),
+        ),
+        (
+            0x0303,
+            vec![sni(&[b"example.com"])],
+            Err(ClientHelloError::PeerIncompatible(
+                "client didn't describe signature schemes",
+            )),
+        ),
+        (
+            0x0303,
+            vec![sigalgs.clone(), sigalgs.clone()],
+            Err(ClientHelloError::PeerMisbehaved(
+                "client sent duplicate extensions",
+            )),
+        ),
+        (
+            0x0303,
+            vec![sigalgs.clone(), sni(&[b"example.com", b"example.org"])],
+            Err(ClientHelloError::PeerMisbehaved(
+                "ClientHello SNI contains duplicate name types",
+            )),
+        ),
+        (
+            0x0303,
+            vec![sigalgs.clone(), sni(&[b"192.168.0.1"])],
+            Err(ClientHelloError::PeerMisbehaved(
+                "ClientHello SNI contains an invalid hostname",
+            )),
+        ),
+        (
+            0x0303,
+            vec![sigalgs.clone(), sni(&[b"example.com."])],
+            Ok(()),
+        ),
+        (
+            0x0303,
+            vec![sigalgs.clone(), (0x0000, server_name(&[(1, b"a")]))],
+            Err(ClientHelloError::PeerMisbehaved(
+                "ClientHello SNI did not contain a hostname",
+            )),
+        ),
+    ] {
+        let chp = ClientHelloPayload::read_bytes(&client_hello_body(
+            version,
+            &[0x1301],
+            Some(&extensions),
+        ))?;
+        assert_eq!(chp.client_hello().validate_for_rustls(), expected);
+    }
+
+    // Checked first, like rustls does.
+    let mut chp =
+        ClientHelloPayload::read_bytes(&minimal_client_hello_body(&[sigalgs.clone(), sigalgs]))?;
+    chp.compression_methods = vec![Compression::Deflate];
+    assert_eq!(
+        chp.client_hello().validate_for_rustls(),
+        Err(ClientHelloError::PeerIncompatible(
+            "client did not offer Null compression"
+        ))
+    );
+    chp.compression_methods.push(Compression::Null);
+    assert_eq!(
+        chp.client_hello().compression_methods(),
+        &[Compression::Deflate, Compression::Null]
+    );
+    assert_eq!(
+        chp.client_hello().validate_for_rustls(),
+        Err(ClientHelloError::PeerMisbehaved(
+            "client sent duplicate extensions"
+        ))
+    );
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use tls_client_hello_parser::{
+    enums::{CipherSuite, ExtensionType},
+    ClientHelloRef, Parser,
+};
+use tls_mkcert_test::SNI;
+
+use common::rustls_client_hello_record;
+
+#[test]
+fn test_parse() -> Result<(), Box<dyn std::error::Error>> {
+    let buf = rustls_client_hello_record()?;
+
+    assert!(ClientHelloRef::parse(&buf[..buf.len() - 1])?.is_none());
+
+    let chr = ClientHelloRef::parse(&buf)?.ok_or("ClientHelloRef::parse none")?;
+    assert_eq!(chr.sni_host_name()?, Some(SNI.as_bytes()));
+    assert!(chr
+        .cipher_suites()
+        .any(|x| x == CipherSuite::TLS13_AES_128_GCM_SHA256));
+    assert!(chr
+        .extensions()
+        .any(|x| x.typ == ExtensionType::SupportedVersions));
+    assert!(chr.find_extension(ExtensionType::ServerName).is_some());
+    assert_eq!(chr.session_id().len(), 32);
+
+    let chp = chr.to_owned()?;
+    assert_eq!(chp.client_version, chr.client_version());
+    assert_eq!(&chp.random.0, chr.random());
+    assert_eq!(chp.extensions.len(), chr.extensions().count());
+    assert_eq!(chp.client_hello().server_name(), Some(SNI));
+
+    let mut parser = Parser::new();
+    assert_eq!(parser.parse_bytes(&buf)?, Some(chp));
+
+    Ok(())
+}
+
+#[test]
+fn test_lazy_extensions() -> Result<(), Box<dyn std::error::Error>> {
+    // ClientHello with a malformed server_name extension payload.
+    let mut body = vec![0x03, 0x03];
+    body.extend_from_slice(&[0; 32]);
+    body.extend_from_slice(&[0x00, 0x00, 0x02, 0x13, 0x01, 0x01, 0x00]);
+    body.extend_from_slice(&[0x00, 0x05, 0x00, 0x00, 0x00, 0x01, 0xff]);
+
+    let chr = ClientHelloRef::read_bytes(&body)?;
+    assert_eq!(chr.extensions().count(), 1);
+    assert!(chr.extensions().next().ok_or("")?.decode().is_err());
+    assert!(chr.sni_host_name().is_err());
+    assert!(chr.to_owned().is_err());
+
+    // Extension framing is checked up front.
+    let mut body = body.clone();
+    body.truncate(body.len() - 1);
+    assert!(ClientHelloRef::read_bytes(&body).is_err());
+
+    Ok(())
+}
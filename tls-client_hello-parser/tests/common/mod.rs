@@ -0,0 +1,206 @@
+//! Helpers shared by the integration tests, each of them only uses some.
+//!
+//! The `*_body` functions write raw bytes, malformed ones included, where `ClientHelloBuilder`
+//! only encodes what the codec accepts.
+
+#![allow(dead_code)]
+
+use std::{io::Cursor, sync::Arc};
+
+use rustls::{ClientConnection, ServerConfig, ServerConnection};
+use tls_client_hello_parser::{
+    enums::{CipherSuite, ECPointFormat, NamedGroup, SignatureScheme},
+    handshake::ClientExtension,
+    ClientHelloBuilder, ClientHelloPayload, Parser, Progress,
+};
+use tls_mkcert_test::{
+    rustls::{make_client_config, make_client_connection},
+    SNI,
+};
+
+//
+/// A ClientHello body with a zero random, no session id and Null compression. `extensions` is the
+/// content of the extensions block, `None` leaves the block out.
+pub fn client_hello_body_raw(
+    version: u16,
+    cipher_suites: &[u16],
+    extensions: Option<&[u8]>,
+) -> Vec<u8> {
+    let mut body = version.to_be_bytes().to_vec();
+    body.extend_from_slice(&[0; 32]);
+    body.push(0);
+    body.extend_from_slice(&u16_list(2, cipher_suites));
+    body.extend_from_slice(&[1, 0]);
+    if let Some(extensions) = extensions {
+        body.extend_from_slice(&(extensions.len() as u16).to_be_bytes());
+        body.extend_from_slice(extensions);
+    }
+    body
+}
+
+pub fn client_hello_body(
+    version: u16,
+    cipher_suites: &[u16],
+    extensions: Option<&[(u16, Vec<u8>)]>,
+) -> Vec<u8> {
+    let extensions = extensions.map(|extensions| {
+        let mut v = Vec::new();
+        for (typ, payload) in extensions {
+            v.extend_from_slice(&typ.to_be_bytes());
+            v.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+            v.extend_from_slice(payload);
+        }
+        v
+    });
+    client_hello_body_raw(version, cipher_suites, extensions.as_deref())
+}
+
+/// TLS 1.2 with TLS_AES_128_GCM_SHA256 only, the extensions start at offset 43.
+pub fn minimal_client_hello_body(extensions: &[(u16, Vec<u8>)]) -> Vec<u8> {
+    client_hello_body(0x0303, &[0x1301], Some(extensions))
+}
+
+/// `len_size` is the size of the length prefix, 1 or 2.
+pub fn u16_list(len_size: usize, list: &[u16]) -> Vec<u8> {
+    let len = (list.len() * 2) as u16;
+    let mut v = len.to_be_bytes()[2 - len_size..].to_vec();
+    for x in list {
+        v.extend_from_slice(&x.to_be_bytes());
+    }
+    v
+}
+
+/// The payload of an application_layer_protocol_negotiation extension.
+pub fn alpn(protocols: &[&[u8]]) -> Vec<u8> {
+    let mut v = Vec::new();
+    for x in protocols {
+        v.push(x.len() as u8);
+        v.extend_from_slice(x);
+    }
+    let mut ret = (v.len() as u16).to_be_bytes().to_vec();
+    ret.extend_from_slice(&v);
+    ret
+}
+
+/// The payload of a server_name extension, from `(name_type, name)` entries.
+pub fn server_name(names: &[(u8, &[u8])]) -> Vec<u8> {
+    let mut v = Vec::new();
+    for (typ, name) in names {
+        v.push(*typ);
+        v.extend_from_slice(&(name.len() as u16).to_be_bytes());
+        v.extend_from_slice(name);
+    }
+    let mut ret = (v.len() as u16).to_be_bytes().to_vec();
+    ret.extend_from_slice(&v);
+    ret
+}
+
+/// Prepends the handshake header to a ClientHello body.
+pub fn handshake_message(body: &[u8]) -> Vec<u8> {
+    let mut message = vec![0x01];
+    message.extend_from_slice(&(body.len() as u32).to_be_bytes()[1..]);
+    message.extend_from_slice(body);
+    message
+}
+
+/// Frames `handshake` into records of exactly `fragment_len` bytes, but the last one.
+///
+/// Unlike `record::encode_records`, record boundaries are where the offsets in the tests expect.
+pub fn frame(handshake: &[u8], fragment_len: usize) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for fragment in handshake.chunks(fragment_len) {
+        buf.extend_from_slice(&[0x16, 0x03, 0x01]);
+        buf.extend_from_slice(&(fragment.len() as u16).to_be_bytes());
+        buf.extend_from_slice(fragment);
+    }
+    buf
+}
+
+//
+/// The first flight of `client`, a single record.
+pub fn client_hello_record(
+    client: &mut ClientConnection,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut buf = Vec::new();
+    client.write_tls(&mut buf)?;
+    Ok(buf)
+}
+
+/// The first flight of a rustls client, a single record.
+pub fn rustls_client_hello_record() -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    client_hello_record(&mut make_client_connection()?)
+}
+
+/// The ClientHello handshake message of a rustls client, without the record header.
+pub fn rustls_client_hello_handshake() -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    Ok(rustls_client_hello_record()?.split_off(5))
+}
+
+pub fn rustls_client_hello() -> Result<ClientHelloPayload, Box<dyn std::error::Error>> {
+    Ok(parse(&rustls_client_hello_record()?))
+}
+
+/// Runs the handshake to completion, the client's session tickets included.
+pub fn complete_handshake(
+    client: &mut ClientConnection,
+    server: &mut ServerConnection,
+) -> Result<(), Box<dyn std::error::Error>> {
+    while client.is_handshaking() || client.wants_write() || server.wants_write() {
+        let mut buf = Vec::new();
+        while client.wants_write() {
+            client.write_tls(&mut buf)?;
+        }
+        server.read_tls(&mut Cursor::new(&buf))?;
+        server.process_new_packets()?;
+
+        let mut buf = Vec::new();
+        while server.wants_write() {
+            server.write_tls(&mut buf)?;
+        }
+        client.read_tls(&mut Cursor::new(&buf))?;
+        client.process_new_packets()?;
+    }
+    Ok(())
+}
+
+/// A TLS 1.3 ClientHello with a pre_shared_key for a session `server_config` just stored.
+pub fn rustls_resumption_client_hello(
+    server_config: &Arc<ServerConfig>,
+) -> Result<ClientHelloPayload, Box<dyn std::error::Error>> {
+    let client_config = Arc::new(make_client_config()?);
+    complete_handshake(
+        &mut ClientConnection::new(client_config.clone(), SNI.try_into()?)?,
+        &mut ServerConnection::new(server_config.clone())?,
+    )?;
+
+    let mut client = ClientConnection::new(client_config, SNI.try_into()?)?;
+    Ok(parse(&client_hello_record(&mut client)?))
+}
+
+/// Parses a whole ClientHello, panics on anything else.
+pub fn parse(buf: &[u8]) -> ClientHelloPayload {
+    match Parser::new().feed(buf) {
+        Ok(Progress::Done(chp, len)) => {
+            assert_eq!(len, buf.len());
+            chp
+        }
+        x => panic!("{x:?}"),
+    }
+}
+
+/// TLS 1.2 only, with ECDHE_RSA suites and the extensions they need.
+pub fn tls12_client() -> ClientHelloBuilder {
+    ClientHelloBuilder::new()
+        .with_cipher_suites([
+            CipherSuite::TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256,
+            CipherSuite::TLS_ECDHE_RSA_WITH_AES_128_CBC_SHA,
+        ])
+        .with_extension(ClientExtension::ECPointFormats(vec![
+            ECPointFormat::Uncompressed,
+        ]))
+        .with_extension(ClientExtension::NamedGroups(vec![NamedGroup::secp256r1]))
+        .with_extension(ClientExtension::SignatureAlgorithms(vec![
+            SignatureScheme::RSA_PSS_SHA256,
+            SignatureScheme::RSA_PKCS1_SHA256,
+        ]))
+}
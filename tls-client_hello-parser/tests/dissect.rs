@@ -0,0 +1,194 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use tls_client_hello_parser::{
+    codec::{PayloadU16, PayloadU8},
+    enums::{CipherSuite, ExtensionType, NamedGroup, ProtocolVersion, ServerNameType},
+    handshake::{ClientExtension, KeyShareEntry, ServerName, UnknownExtension},
+    ClientHelloBuilder,
+};
+
+use common::{parse, rustls_client_hello_record};
+
+fn builder() -> ClientHelloBuilder {
+    ClientHelloBuilder::new()
+        .with_random([0xab; 32])
+        .with_cipher_suites([
+            CipherSuite::Unknown(0x1a1a),
+            CipherSuite::TLS13_AES_128_GCM_SHA256,
+        ])
+        .with_extension(ClientExtension::ServerName(vec![ServerName {
+            typ: ServerNameType::HostName,
+            payload: PayloadU16(b"example.com".to_vec()),
+        }]))
+        .with_extension(ClientExtension::Protocols(vec![
+            PayloadU8(b"\x0a\x0a".to_vec()),
+            PayloadU8(b"h2".to_vec()),
+            PayloadU8(b"\x00\x01".to_vec()),
+        ]))
+        .with_extension(ClientExtension::SupportedVersions(vec![
+            ProtocolVersion::TLSv1_3,
+        ]))
+        .with_extension(ClientExtension::KeyShare(vec![KeyShareEntry {
+            group: NamedGroup::X25519,
+            payload: PayloadU16(vec![1; 32]),
+        }]))
+        .with_extension(ClientExtension::Unknown(UnknownExtension {
+            typ: ExtensionType::Padding,
+            payload: vec![0; 100],
+        }))
+        .with_extension(ClientExtension::Unknown(UnknownExtension {
+            typ: ExtensionType::Unknown(0x7a7b),
+            payload: vec![1, 2],
+        }))
+}
+
+#[test]
+fn test_dissect() {
+    let chp = builder().build();
+
+    let expected = format!(
+        "\
+Handshake Protocol: ClientHello
+    Handshake Type: ClientHello (1)
+    Length: 239
+    Version: TLSv1_2 (0x0303)
+    Random: {}
+    Session ID Length: 0
+    Cipher Suites Length: 4
+    Cipher Suites (2 suites)
+        Cipher Suite: GREASE (0x1a1a)
+        Cipher Suite: TLS_AES_128_GCM_SHA256 (0x1301)
+    Compression Methods Length: 1
+    Compression Methods (1 method)
+        Compression Method: Null (0)
+    Extensions Length: 194
+    Extension: server_name (0x0000)
+        Length: 16
+        Server Name Type: HostName (0)
+        Server Name: example.com
+    Extension: application_layer_protocol_negotiation (0x0010)
+        Length: 11
+        ALPN Protocol: GREASE (0x0a0a)
+        ALPN Protocol: h2
+        ALPN Protocol: 0x0001
+    Extension: supported_versions (0x002b)
+        Length: 3
+        Supported Version: TLSv1_3 (0x0304)
+    Extension: key_share (0x0033)
+        Length: 38
+        Key Share Entry: Group: x25519 (0x001d), Key Exchange Length: 32
+    Extension: padding (0x0015)
+        Length: 100
+        Data: {}...
+    Extension: Unknown (0x7a7b)
+        Length: 2
+        Data: 0102
+",
+        "ab".repeat(32),
+        "00".repeat(64)
+    );
+    assert_eq!(chp.dissect().to_string(), expected);
+
+    assert_eq!(
+        chp.dissect().summary().to_string(),
+        format!(
+            "ClientHello version=TLSv1_3 sni=example.com alpn=GREASE (0x0a0a),h2,0x0001 \
+             cipher_suites=2 extensions=6 ja4={}",
+            chp.ja4(tls_client_hello_parser::ja4::Transport::Tcp)
+        )
+    );
+}
+
+#[test]
+fn test_dissect_records() -> Result<(), Box<dyn std::error::Error>> {
+    let builder = builder().with_records(2);
+    let buf = builder.to_record_bytes()?;
+    let chp = parse(&buf);
+
+    let dissection = chp.dissect().with_records(&buf).to_string();
+    assert!(dissection.starts_with(
+        "\
+Record Layer
+    Content Type: Handshake (22)
+    Version: TLSv1_0 (0x0301)
+    Length: 122
+Record Layer
+    Content Type: Handshake (22)
+    Version: TLSv1_0 (0x0301)
+    Length: 121
+Handshake Protocol: ClientHello
+"
+    ));
+    assert!(dissection.ends_with(&chp.dissect().to_string()));
+
+    // Records after the ClientHello are left out.
+    let mut input = buf.clone();
+    input.extend_from_slice(&[0x15, 0x03, 0x03, 0x00, 0x02, 0x02, 0x28]);
+    assert_eq!(chp.dissect().with_records(&input).to_string(), dissection);
+
+    Ok(())
+}
+
+#[test]
+fn test_dissect_minimal() {
+    let chp = ClientHelloBuilder::new().build();
+    let dissection = chp.dissect().to_string();
+    assert!(dissection.ends_with("Compression Method: Null (0)\n"));
+    assert_eq!(
+        chp.dissect().summary().to_string(),
+        "ClientHello version=TLSv1_2 sni=- alpn=- cipher_suites=0 extensions=0 \
+         ja4=t12i000000_000000000000_000000000000"
+    );
+
+    let chp = ClientHelloBuilder::new()
+        .with_empty_extensions_block(true)
+        .build();
+    assert!(chp
+        .dissect()
+        .to_string()
+        .ends_with("Compression Method: Null (0)\n    Extensions Length: 0\n"));
+}
+
+#[test]
+fn test_dissect_rustls() -> Result<(), Box<dyn std::error::Error>> {
+    let buf = rustls_client_hello_record()?;
+
+    let chp = parse(&buf);
+    let dissection = chp.dissect().with_records(&buf).to_string();
+    assert_eq!(dissection.matches("Record Layer\n").count(), 1);
+    assert!(dissection.contains(
+        "    Extension: server_name (0x0000)\n        Length: 15\n        \
+         Server Name Type: HostName (0)\n        Server Name: tls.lvh.me\n"
+    ));
+    assert!(chp
+        .dissect()
+        .summary()
+        .to_string()
+        .starts_with("ClientHello version=TLSv1_3 sni=tls.lvh.me alpn=- "));
+
+    Ok(())
+}
+
+#[test]
+fn test_dissect_server_name_escaped() {
+    for (name, expected) in [
+        (&b"a.example\n    Extension: fake"[..], None),
+        ("é.example".as_bytes(), None),
+        (b"a b.example", None),
+        (b"a.example", Some("a.example")),
+    ] {
+        let chp = ClientHelloBuilder::new()
+            .with_extension(ClientExtension::ServerName(vec![ServerName {
+                typ: ServerNameType::HostName,
+                payload: PayloadU16(name.to_vec()),
+            }]))
+            .build();
+        let hex = name.iter().map(|x| format!("{x:02x}")).collect::<String>();
+        let expected = format!("Server Name: {}\n", expected.unwrap_or(&hex));
+        let dissection = chp.dissect().to_string();
+        assert!(dissection.ends_with(&expected), "{dissection}");
+        assert!(!dissection.contains("Extension: fake"));
+    }
+}
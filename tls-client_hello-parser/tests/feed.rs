@@ -0,0 +1,190 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use tls_client_hello_parser::{
+    enums::{ContentType, ExtensionType},
+    handshake::{ClientExtension, UnknownExtension},
+    ClientHelloBuilder, Limit, ParseError, Parser, ParserConfig, Progress,
+};
+use tls_mkcert_test::SNI;
+
+use common::{frame, rustls_client_hello_handshake};
+
+// A record per byte is over the default `max_records`.
+fn fragmenting_parser() -> Parser {
+    Parser::with_config(ParserConfig {
+        max_records: usize::MAX,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_feed_byte_by_byte() -> Result<(), Box<dyn std::error::Error>> {
+    let handshake = rustls_client_hello_handshake()?;
+
+    for fragment_len in [handshake.len(), 100, 3, 1] {
+        let buf = frame(&handshake, fragment_len);
+        let mut parser = fragmenting_parser();
+
+        let (buf, last) = buf.split_at(buf.len() - 1);
+        for (i, b) in buf.iter().enumerate() {
+            match parser.feed(&[*b])? {
+                Progress::NeedMore { at_least } => {
+                    assert!(at_least >= 1);
+                    assert!(at_least <= buf.len() - i, "{fragment_len} {i} {at_least}");
+                }
+                x => panic!("{fragment_len} {i} {x:?}"),
+            }
+        }
+
+        let mut last = last.to_vec();
+        // The first bytes of the next record.
+        last.extend_from_slice(&[0x14, 0x03, 0x03, 0x00, 0x01, 0x01]);
+        match parser.feed(&last)? {
+            Progress::Done(chp, consumed) => {
+                assert_eq!(consumed, buf.len() + 1);
+                assert_eq!(chp.client_hello().server_name(), Some(SNI));
+            }
+            x => panic!("{fragment_len} {x:?}"),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_feed_at_least() -> Result<(), Box<dyn std::error::Error>> {
+    let handshake = rustls_client_hello_handshake()?;
+
+    for fragment_len in [handshake.len(), 100, 3] {
+        let buf = frame(&handshake, fragment_len);
+        let mut parser = fragmenting_parser();
+
+        // Nothing fed yet, a record header and a handshake header are needed.
+        let mut at_least = match parser.feed(&[])? {
+            Progress::NeedMore { at_least } => at_least,
+            x => panic!("{x:?}"),
+        };
+        assert_eq!(at_least, 9);
+
+        let mut offset = 0;
+        let mut feeds = 0;
+        loop {
+            feeds += 1;
+            match parser.feed(&buf[offset..offset + at_least])? {
+                Progress::NeedMore { at_least: x } => {
+                    offset += at_least;
+                    at_least = x;
+                }
+                Progress::Done(_, consumed) => {
+                    assert_eq!(offset + at_least, buf.len());
+                    assert_eq!(consumed, buf.len());
+                    break;
+                }
+                x => panic!("{x:?}"),
+            }
+        }
+
+        // A single record is read as the headers, then the rest of the record.
+        if fragment_len == handshake.len() {
+            assert_eq!(feeds, 2);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_feed_not_tls() -> Result<(), Box<dyn std::error::Error>> {
+    for bytes in [
+        &b"GET / HTTP/1.1\r\n"[..],
+        b"SSH-2.0-OpenSSH_9.6\r\n",
+        b"\x16\x02\x00\x00\x01",
+        b"\x00\x03\x01\x00\x01",
+    ] {
+        let mut parser = Parser::new();
+        assert_eq!(parser.feed(bytes)?, Progress::NotTls, "{bytes:?}");
+    }
+
+    let mut parser = Parser::new();
+    assert_eq!(
+        parser.feed(b"\x16\x03")?,
+        Progress::NeedMore { at_least: 3 }
+    );
+    assert_eq!(parser.feed(b"\x01")?, Progress::NeedMore { at_least: 2 });
+    assert_eq!(parser.feed(b"\x00\x00")?, Progress::NotTls);
+
+    // Decided from the first byte.
+    let mut parser = Parser::new();
+    assert_eq!(parser.feed(b"G")?, Progress::NotTls);
+
+    let mut parser = Parser::new();
+    assert_eq!(
+        parser.feed(&[0x15, 0x03, 0x03, 0x00, 0x02, 0x02, 0x28])?,
+        Progress::NotTls
+    );
+
+    // A valid first record followed by a non-handshake record.
+    let mut parser = Parser::new();
+    match parser.feed(&[
+        0x16, 0x03, 0x01, 0x00, 0x01, 0x01, 0x15, 0x03, 0x03, 0x00, 0x02, 0x02, 0x28,
+    ]) {
+        Err(ParseError::NotHandshake {
+            content_type: ContentType::Alert,
+            offset: 6,
+        }) => {}
+        x => panic!("{x:?}"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_feed_many_records() -> Result<(), Box<dyn std::error::Error>> {
+    let handshake = rustls_client_hello_handshake()?;
+    let buf = frame(&handshake, 1);
+
+    // The 65th record is rejected by default.
+    let mut parser = Parser::new();
+    let err = buf
+        .iter()
+        .map(|b| parser.feed(&[*b]))
+        .find_map(Result::err)
+        .expect("LimitExceeded");
+    assert!(matches!(
+        err,
+        ParseError::LimitExceeded {
+            limit: Limit::Records,
+            offset: 384
+        }
+    ));
+
+    // 20000 records of a byte or two fed a byte at a time, the parser resumes where it stopped
+    // instead of going over the records it already saw on every feed.
+    let buf = ClientHelloBuilder::new()
+        .with_extension(ClientExtension::Unknown(UnknownExtension {
+            typ: ExtensionType::Padding,
+            payload: vec![0; 20000],
+        }))
+        .with_records(20000)
+        .to_record_bytes()?;
+    assert!(buf.len() > 20000 * 6);
+
+    let mut parser = Parser::with_config(ParserConfig {
+        max_records: 20000,
+        ..Default::default()
+    });
+    let started = Instant::now();
+    let (buf, last) = buf.split_at(buf.len() - 1);
+    for b in buf {
+        assert!(matches!(parser.feed(&[*b])?, Progress::NeedMore { .. }));
+    }
+    assert!(matches!(parser.feed(last)?, Progress::Done(..)));
+    // Minutes in a debug build when quadratic.
+    assert!(started.elapsed() < Duration::from_secs(10));
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use tls_client_hello_parser::{
+    codec::Codec as _,
+    enums::{
+        is_grease_alpn_protocol, CipherSuite, ExtensionType, NamedGroup, ProtocolVersion,
+        SignatureScheme,
+    },
+    ClientHelloPayload,
+};
+
+use common::{alpn, client_hello_body, u16_list};
+
+#[test]
+fn test_is_grease() {
+    for i in 0..=0x0f_u16 {
+        let x = i << 12 | 0x0a00 | i << 4 | 0x0a;
+        assert!(CipherSuite::from(x).is_grease());
+        assert!(ExtensionType::from(x).is_grease());
+        assert!(NamedGroup::from(x).is_grease());
+        assert!(ProtocolVersion::from(x).is_grease());
+        assert!(SignatureScheme::from(x).is_grease());
+        assert!(is_grease_alpn_protocol(&x.to_be_bytes()));
+    }
+
+    assert!(!CipherSuite::from(0x0a1a).is_grease());
+    assert!(!CipherSuite::TLS13_AES_128_GCM_SHA256.is_grease());
+    assert!(!ExtensionType::ServerName.is_grease());
+    assert!(!NamedGroup::X25519.is_grease());
+    assert!(!ProtocolVersion::TLSv1_3.is_grease());
+    assert!(!SignatureScheme::ED25519.is_grease());
+    assert!(!is_grease_alpn_protocol(b"h2"));
+    assert!(!is_grease_alpn_protocol(&[0x0a]));
+    assert!(!is_grease_alpn_protocol(&[0x0a, 0x0a, 0x0a]));
+}
+
+#[test]
+fn test_without_grease() -> Result<(), Box<dyn std::error::Error>> {
+    let chp = ClientHelloPayload::read_bytes(&client_hello_body(
+        0x0303,
+        &[0x1a1a, 0x1301, 0xc02f],
+        Some(&[
+            (0x2a2a, vec![]),
+            (0x000a, u16_list(2, &[0x3a3a, 0x001d, 0x0017])),
+            (0x000d, u16_list(2, &[0x0403, 0x4a4a, 0x0804])),
+            (0x0010, alpn(&[&[0x5a, 0x5a], b"h2", b"http/1.1"])),
+            (0x002b, u16_list(1, &[0x6a6a, 0x0304, 0x0303])),
+            (0xfafa, vec![0]),
+        ]),
+    ))?;
+
+    assert_eq!(chp.cipher_suites.len(), 3);
+    assert_eq!(
+        chp.cipher_suites_without_grease().collect::<Vec<_>>(),
+        [
+            CipherSuite::TLS13_AES_128_GCM_SHA256,
+            CipherSuite::TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256
+        ]
+    );
+
+    assert_eq!(chp.extensions.len(), 6);
+    assert_eq!(
+        chp.extensions_without_grease()
+            .map(|x| x.get_type())
+            .collect::<Vec<_>>(),
+        [
+            ExtensionType::EllipticCurves,
+            ExtensionType::SignatureAlgorithms,
+            ExtensionType::ALProtocolNegotiation,
+            ExtensionType::SupportedVersions,
+        ]
+    );
+
+    assert_eq!(chp.get_namedgroups_extension().map(|x| x.len()), Some(3));
+    assert_eq!(
+        chp.namedgroups_without_grease()
+            .map(|x| x.collect::<Vec<_>>()),
+        Some(vec![NamedGroup::X25519, NamedGroup::secp256r1])
+    );
+
+    assert_eq!(
+        chp.sigalgs_without_grease().map(|x| x.collect::<Vec<_>>()),
+        Some(vec![
+            SignatureScheme::ECDSA_NISTP256_SHA256,
+            SignatureScheme::RSA_PSS_SHA256
+        ])
+    );
+
+    assert_eq!(
+        chp.alpn_without_grease().map(|x| x.collect::<Vec<_>>()),
+        Some(vec![&b"h2"[..], &b"http/1.1"[..]])
+    );
+
+    assert_eq!(
+        chp.versions_without_grease().map(|x| x.collect::<Vec<_>>()),
+        Some(vec![ProtocolVersion::TLSv1_3, ProtocolVersion::TLSv1_2])
+    );
+
+    let client_hello = chp.client_hello();
+    assert_eq!(client_hello.cipher_suites().len(), 3);
+    assert_eq!(client_hello.cipher_suites_without_grease().count(), 2);
+    assert_eq!(client_hello.signature_schemes().map(|x| x.len()), Some(3));
+    assert_eq!(
+        client_hello
+            .signature_schemes_without_grease()
+            .map(|x| x.count()),
+        Some(2)
+    );
+    assert_eq!(client_hello.alpn().map(|x| x.count()), Some(3));
+    assert_eq!(
+        client_hello
+            .alpn_without_grease()
+            .map(|x| x.collect::<Vec<_>>()),
+        Some(vec![&b"h2"[..], &b"http/1.1"[..]])
+    );
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+#![cfg(feature = "std")]
+
+use tls_client_hello_parser::{
+    enums::{CipherSuite, ExtensionType, NamedGroup, SignatureScheme},
+    iana::{self, Entry, Recommended},
+};
+
+#[test]
+fn test_lookup() {
+    assert_eq!(
+        CipherSuite::TLS13_AES_128_GCM_SHA256.iana(),
+        Some(&Entry {
+            name: "TLS_AES_128_GCM_SHA256",
+            reference: "[RFC8446]",
+            recommended: Recommended::Yes,
+        })
+    );
+    assert_eq!(
+        iana::cipher_suite(0xc02f).map(|x| x.name),
+        Some("TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256")
+    );
+    assert_eq!(
+        iana::cipher_suite(0x0005).map(|x| x.recommended),
+        Some(Recommended::Discouraged)
+    );
+
+    assert_eq!(
+        NamedGroup::X25519.iana().map(|x| (x.name, x.reference)),
+        Some(("x25519", "[RFC8446][RFC8422]"))
+    );
+    assert_eq!(
+        iana::supported_group(0x11ec).map(|x| x.name),
+        Some("X25519MLKEM768")
+    );
+    assert_eq!(
+        iana::supported_group(1).map(|x| x.recommended),
+        Some(Recommended::Discouraged)
+    );
+
+    assert_eq!(
+        SignatureScheme::RSA_PSS_SHA256.iana().map(|x| x.name),
+        Some("rsa_pss_rsae_sha256")
+    );
+    assert_eq!(
+        SignatureScheme::RSA_PKCS1_SHA1
+            .iana()
+            .map(|x| x.recommended),
+        Some(Recommended::Discouraged)
+    );
+
+    assert_eq!(
+        ExtensionType::ALProtocolNegotiation.iana().map(|x| x.name),
+        Some("application_layer_protocol_negotiation")
+    );
+    assert_eq!(
+        ExtensionType::Unknown(0xfe0d).iana().map(|x| x.name),
+        Some("encrypted_client_hello")
+    );
+}
+
+#[test]
+fn test_lookup_ranges() {
+    // GREASE values are reserved.
+    for x in [0x0a0a, 0x5a5a, 0xfafa] {
+        for entry in [
+            iana::cipher_suite(x),
+            iana::supported_group(x),
+            iana::signature_scheme(x),
+            iana::extension_type(x),
+        ] {
+            assert_eq!(
+                entry.map(|x| (x.name, x.reference)),
+                Some(("Reserved", "[RFC8701]"))
+            );
+        }
+    }
+
+    // Ranges, bounds included.
+    for x in [0xff00, 0xff7f, 0xffff] {
+        assert_eq!(
+            iana::cipher_suite(x).map(|x| x.name),
+            Some("Reserved for Private Use")
+        );
+    }
+    assert_eq!(
+        iana::cipher_suite(0x001d).map(|x| x.name),
+        Some("Reserved to avoid conflicts with SSLv3")
+    );
+    assert_eq!(
+        iana::supported_group(0xfe00).map(|x| x.name),
+        Some("Reserved for Private Use")
+    );
+    assert_eq!(
+        iana::signature_scheme(0x0200).map(|x| x.recommended),
+        Some(Recommended::No)
+    );
+    assert_eq!(
+        iana::extension_type(0xff01).map(|x| x.name),
+        Some("renegotiation_info")
+    );
+    assert_eq!(
+        iana::extension_type(0xffff).map(|x| x.name),
+        Some("Reserved for Private Use")
+    );
+
+    // Unassigned.
+    assert_eq!(iana::cipher_suite(0x005e), None);
+    assert_eq!(iana::cipher_suite(0xc107), None);
+    assert_eq!(iana::supported_group(42), None);
+    assert_eq!(iana::signature_scheme(0x0900), None);
+    assert_eq!(iana::extension_type(0x7a7b), None);
+}
+
+#[test]
+fn test_known_values_registered() {
+    for x in 0..=u16::MAX {
+        let cipher_suite = CipherSuite::from(x);
+        if !matches!(cipher_suite, CipherSuite::Unknown(_)) {
+            assert!(cipher_suite.iana().is_some(), "{cipher_suite:?}");
+        }
+        let group = NamedGroup::from(x);
+        if !matches!(group, NamedGroup::Unknown(_)) {
+            assert!(group.iana().is_some(), "{group:?}");
+        }
+        let scheme = SignatureScheme::from(x);
+        if !matches!(scheme, SignatureScheme::Unknown(_)) {
+            assert!(scheme.iana().is_some(), "{scheme:?}");
+        }
+        // Not registered with IANA.
+        let ext_type = ExtensionType::from(x);
+        if !matches!(
+            ext_type,
+            ExtensionType::Unknown(_)
+                | ExtensionType::NextProtocolNegotiation
+                | ExtensionType::ApplicationSettings
+                | ExtensionType::ChannelId
+        ) {
+            assert!(ext_type.iana().is_some(), "{ext_type:?}");
+        }
+    }
+}
@@ -0,0 +1,115 @@
+#![cfg(all(feature = "std", feature = "idna"))]
+
+mod common;
+
+use tls_client_hello_parser::{
+    codec::Codec as _,
+    server_name::{idn_host_name_to_unicode, normalize_idn_host_name},
+    ClientHelloPayload, ServerNameError,
+};
+
+use common::{minimal_client_hello_body, server_name};
+
+#[test]
+fn test_normalize_idn_host_name() {
+    let invalid = Err(ServerNameError::InvalidIdn);
+
+    for (name, ascii, unicode) in [
+        ("example.com", Ok("example.com"), Ok("example.com")),
+        ("Example.COM.", Ok("example.com"), Ok("example.com")),
+        (
+            "B\u{fc}cher.example",
+            Ok("xn--bcher-kva.example"),
+            Ok("b\u{fc}cher.example"),
+        ),
+        (
+            "XN--BCHER-KVA.example",
+            Ok("xn--bcher-kva.example"),
+            Ok("b\u{fc}cher.example"),
+        ),
+        // Nontransitional processing keeps the sharp s.
+        ("fa\u{df}.de", Ok("xn--fa-hia.de"), Ok("fa\u{df}.de")),
+        ("FASS.de", Ok("fass.de"), Ok("fass.de")),
+        (
+            "\u{65e5}\u{672c}\u{8a9e}.jp",
+            Ok("xn--wgv71a119e.jp"),
+            Ok("\u{65e5}\u{672c}\u{8a9e}.jp"),
+        ),
+        ("\u{2615}.us", Ok("xn--53h.us"), Ok("\u{2615}.us")),
+        (
+            "\u{3b1}\u{3b2}\u{3b3}.gr",
+            Ok("xn--mxacd.gr"),
+            Ok("\u{3b1}\u{3b2}\u{3b3}.gr"),
+        ),
+        // Full-width letters and ideographic full stop.
+        (
+            "\u{ff21}\u{ff22}\u{ff23}\u{3002}com",
+            Ok("abc.com"),
+            Ok("abc.com"),
+        ),
+        ("a\u{2024}com", invalid, invalid),
+        // ZERO WIDTH JOINER outside of a valid context.
+        ("a\u{200d}b.example", invalid, invalid),
+        ("xn--a.example", invalid, invalid),
+        ("a b.example", invalid, invalid),
+        (
+            "\u{ff11}\u{ff10}.0.0.1",
+            Err(ServerNameError::IpAddress),
+            Err(ServerNameError::IpAddress),
+        ),
+    ] {
+        assert_eq!(
+            normalize_idn_host_name(name.as_bytes())
+                .as_deref()
+                .map_err(|x| *x),
+            ascii,
+            "{name:?}"
+        );
+        assert_eq!(
+            idn_host_name_to_unicode(name.as_bytes())
+                .as_deref()
+                .map_err(|x| *x),
+            unicode,
+            "{name:?}"
+        );
+    }
+
+    assert_eq!(
+        normalize_idn_host_name(b"\xff.example"),
+        Err(ServerNameError::InvalidIdn)
+    );
+}
+
+#[test]
+fn test_client_hello() -> Result<(), Box<dyn std::error::Error>> {
+    let chp = ClientHelloPayload::read_bytes(&minimal_client_hello_body(&[(
+        0x0000,
+        server_name(&[(0, b"XN--BCHER-KVA.Example.")]),
+    )]))?;
+    let client_hello = chp.client_hello();
+    assert_eq!(
+        client_hello.normalized_idn_server_name()?.as_deref(),
+        Some("xn--bcher-kva.example")
+    );
+    assert_eq!(
+        client_hello.unicode_server_name()?.as_deref(),
+        Some("b\u{fc}cher.example")
+    );
+
+    let chp = ClientHelloPayload::read_bytes(&minimal_client_hello_body(&[(
+        0x0000,
+        server_name(&[(0, "b\u{fc}cher.example".as_bytes())]),
+    )]))?;
+    let client_hello = chp.client_hello();
+    assert_eq!(client_hello.server_name(), None);
+    assert_eq!(
+        client_hello.normalized_server_name(),
+        Err(ServerNameError::InvalidCharacter(0xc3))
+    );
+    assert_eq!(
+        client_hello.normalized_idn_server_name()?.as_deref(),
+        Some("xn--bcher-kva.example")
+    );
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use tls_client_hello_parser::{codec::Codec as _, ClientHelloPayload};
+
+use common::{client_hello_body, server_name, u16_list};
+
+// https://github.com/salesforce/ja3/blob/master/README.md
+#[test]
+fn test_ja3() -> Result<(), Box<dyn std::error::Error>> {
+    let cipher_suites = [47, 53, 5, 10, 49161, 49162, 49171, 49172, 50, 56, 19, 4];
+
+    let chp = ClientHelloPayload::read_bytes(&client_hello_body(
+        769,
+        &cipher_suites,
+        Some(&[
+            (0, server_name(&[(0, b"example.com")])),
+            (10, u16_list(2, &[23, 24, 25])),
+            (11, vec![1, 0]),
+        ]),
+    ))?;
+    assert_eq!(
+        chp.ja3_string(),
+        "769,47-53-5-10-49161-49162-49171-49172-50-56-19-4,0-10-11,23-24-25,0"
+    );
+    assert_eq!(chp.ja3_hash(), "ada70206e40642a3e4461f35503241d5");
+
+    let chp = ClientHelloPayload::read_bytes(&client_hello_body(
+        769,
+        &[4, 5, 10, 9, 100, 98, 3, 6, 19, 18, 99],
+        None,
+    ))?;
+    assert_eq!(chp.ja3_string(), "769,4-5-10-9-100-98-3-6-19-18-99,,,");
+    assert_eq!(chp.ja3_hash(), "de350869b8c85de67a350c8d186f11e6");
+
+    Ok(())
+}
+
+#[test]
+fn test_ja3_grease() -> Result<(), Box<dyn std::error::Error>> {
+    let chp = ClientHelloPayload::read_bytes(&client_hello_body(
+        769,
+        &[
+            0x0a0a, 47, 53, 5, 10, 49161, 49162, 49171, 49172, 50, 56, 19, 4,
+        ],
+        Some(&[
+            (0x1a1a, vec![]),
+            (0, server_name(&[(0, b"example.com")])),
+            (10, u16_list(2, &[0x2a2a, 23, 24, 25])),
+            (11, vec![1, 0]),
+            (0xfafa, vec![0]),
+        ]),
+    ))?;
+    assert_eq!(
+        chp.ja3_string(),
+        "769,47-53-5-10-49161-49162-49171-49172-50-56-19-4,0-10-11,23-24-25,0"
+    );
+    assert_eq!(chp.ja3_hash(), "ada70206e40642a3e4461f35503241d5");
+
+    // Extension order is kept as sent.
+    let chp = ClientHelloPayload::read_bytes(&client_hello_body(
+        771,
+        &[0x1301],
+        Some(&[(11, vec![1, 0]), (0, server_name(&[(0, b"example.com")]))]),
+    ))?;
+    assert_eq!(chp.ja3_string(), "771,4865,11-0,,0");
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use tls_client_hello_parser::{codec::Codec as _, ja4::Transport, ClientHelloPayload};
+
+use common::{alpn, client_hello_body, server_name, u16_list};
+
+fn chrome_extensions(protocols: &[&[u8]]) -> Vec<(u16, Vec<u8>)> {
+    let mut key_share = vec![
+        0x00, 0x29, 0x1a, 0x1a, 0x00, 0x01, 0x00, 0x00, 0x1d, 0x00, 0x20,
+    ];
+    key_share.extend_from_slice(&[0; 32]);
+
+    vec![
+        (0x0a0a, vec![]),
+        (0x0000, server_name(&[(0, b"example.com")])),
+        (0x0017, vec![]),
+        (0xff01, vec![0x00]),
+        (0x000a, u16_list(2, &[0x2a2a, 0x001d, 0x0017, 0x0018])),
+        (0x000b, vec![0x01, 0x00]),
+        (0x0023, vec![]),
+        (0x0010, alpn(protocols)),
+        (0x0005, vec![0x01, 0x00, 0x00, 0x00, 0x00]),
+        (
+            0x000d,
+            u16_list(
+                2,
+                &[
+                    0x0403, 0x0804, 0x0401, 0x0503, 0x0805, 0x0501, 0x0806, 0x0601,
+                ],
+            ),
+        ),
+        (0x0012, vec![]),
+        (0x0033, key_share),
+        (0x002d, vec![0x01, 0x01]),
+        (0x002b, u16_list(1, &[0x3a3a, 0x0304, 0x0303])),
+        (0x001b, vec![0x02, 0x00, 0x02]),
+        (0x4469, vec![0x00, 0x03, 0x02, b'h', b'2']),
+        (0xbaba, vec![0x00]),
+        (0x0015, vec![0; 16]),
+    ]
+}
+
+const CHROME_CIPHER_SUITES: &[u16] = &[
+    0x5a5a, 0x1301, 0x1302, 0x1303, 0xc02b, 0xc02f, 0xc02c, 0xc030, 0xcca9, 0xcca8, 0xc013, 0xc014,
+    0x009c, 0x009d, 0x002f, 0x0035,
+];
+
+// https://github.com/FoxIO-LLC/ja4/blob/main/technical_details/JA4.md
+#[test]
+fn test_ja4() -> Result<(), Box<dyn std::error::Error>> {
+    let chp = ClientHelloPayload::read_bytes(&client_hello_body(
+        0x0303,
+        CHROME_CIPHER_SUITES,
+        Some(&chrome_extensions(&[b"h2", b"http/1.1"])),
+    ))?;
+
+    assert_eq!(
+        chp.ja4(Transport::Tcp),
+        "t13d1516h2_8daaf6152771_e5627efa2ab1"
+    );
+    assert_eq!(
+        chp.ja4(Transport::Quic),
+        "q13d1516h2_8daaf6152771_e5627efa2ab1"
+    );
+    assert_eq!(
+        chp.ja4_r(Transport::Tcp),
+        "t13d1516h2_002f,0035,009c,009d,1301,1302,1303,c013,c014,c02b,c02c,c02f,c030,cca8,cca9_0005,000a,000b,000d,0012,0015,0017,001b,0023,002b,002d,0033,4469,ff01_0403,0804,0401,0503,0805,0501,0806,0601"
+    );
+
+    // The extension order doesn't matter.
+    let mut extensions = chrome_extensions(&[b"h2", b"http/1.1"]);
+    extensions.reverse();
+    let chp = ClientHelloPayload::read_bytes(&client_hello_body(
+        0x0303,
+        CHROME_CIPHER_SUITES,
+        Some(&extensions),
+    ))?;
+    assert_eq!(
+        chp.ja4(Transport::Tcp),
+        "t13d1516h2_8daaf6152771_e5627efa2ab1"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_ja4_a() -> Result<(), Box<dyn std::error::Error>> {
+    // No extensions at all.
+    let chp = ClientHelloPayload::read_bytes(&client_hello_body(0x0301, &[0x002f, 0x0035], None))?;
+    assert_eq!(
+        chp.ja4(Transport::Tcp),
+        "t10i020000_f54dd463d39b_000000000000"
+    );
+    assert_eq!(chp.ja4_r(Transport::Tcp), "t10i020000_002f,0035_");
+
+    // SSL versions, from the legacy version field.
+    for (version, expected) in [
+        (0x0300, "ts3i020000_"),
+        (0x0002, "ts2i020000_"),
+        (0x0100, "ts1i020000_"),
+        (0x0200, "t00i020000_"),
+    ] {
+        let chp =
+            ClientHelloPayload::read_bytes(&client_hello_body(version, &[0x002f, 0x0035], None))?;
+        assert!(
+            chp.ja4(Transport::Tcp).starts_with(expected),
+            "{}",
+            chp.ja4(Transport::Tcp)
+        );
+    }
+
+    // ALPN values that don't start and end with an alphanumeric character.
+    let mut extensions = chrome_extensions(&[&[0xab, 0xcd]]);
+    extensions.retain(|(typ, _)| *typ != 0x002b);
+    let chp = ClientHelloPayload::read_bytes(&client_hello_body(
+        0x0303,
+        CHROME_CIPHER_SUITES,
+        Some(&extensions),
+    ))?;
+    assert!(chp.ja4(Transport::Tcp).starts_with("t12d1515ad_"));
+
+    let mut extensions = chrome_extensions(&[b"h"]);
+    extensions.retain(|(typ, _)| *typ != 0x0000);
+    let chp = ClientHelloPayload::read_bytes(&client_hello_body(
+        0x0303,
+        CHROME_CIPHER_SUITES,
+        Some(&extensions),
+    ))?;
+    assert!(chp.ja4(Transport::Quic).starts_with("q13i1515hh_"));
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use tls_client_hello_parser::{
+    codec::Codec as _,
+    enums::{ContentType, ExtensionType},
+    handshake::{ClientExtension, UnknownExtension},
+    message::read_client_hello_message,
+    ClientHelloPayload, ClientHelloRef, ParseError, Parser, ParserConfig,
+};
+use tls_mkcert_test::SNI;
+
+use common::{frame, rustls_client_hello_handshake};
+
+#[test]
+fn test_fragmented() -> Result<(), Box<dyn std::error::Error>> {
+    let handshake = rustls_client_hello_handshake()?;
+
+    for fragment_len in [1, 2, 3, 4, 5, 7, 64, handshake.len() - 1] {
+        let buf = frame(&handshake, fragment_len);
+
+        let msg = read_client_hello_message(&buf)?.ok_or("read_client_hello_message none")?;
+        assert_eq!(msg.body.as_ref(), &handshake[4..]);
+        assert_eq!(msg.len, buf.len());
+
+        assert!(matches!(
+            ClientHelloRef::parse(&buf),
+            Err(ParseError::MessageFragmented)
+        ));
+        let chr = ClientHelloRef::read_bytes(&msg.body)?;
+        assert_eq!(chr.sni_host_name()?, Some(SNI.as_bytes()));
+
+        let mut parser = Parser::with_config(ParserConfig {
+            max_records: usize::MAX,
+            ..Default::default()
+        });
+        let (buf, buf_last_byte) = buf.split_at(buf.len() - 1);
+        for bytes in buf.chunks(3) {
+            match parser.parse_bytes(bytes) {
+                Ok(None) => {}
+                x => panic!("{fragment_len} {x:?}"),
+            }
+        }
+        match parser.parse_bytes(buf_last_byte) {
+            Ok(Some(chp)) => {
+                assert_eq!(chp.client_hello().server_name(), Some(SNI));
+            }
+            x => panic!("{fragment_len} {x:?}"),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_larger_than_record() -> Result<(), Box<dyn std::error::Error>> {
+    let handshake = rustls_client_hello_handshake()?;
+
+    let mut chp = ClientHelloPayload::read_bytes(&handshake[4..])?;
+    chp.extensions.insert(
+        0,
+        ClientExtension::Unknown(UnknownExtension {
+            typ: ExtensionType::Padding,
+            payload: vec![0; 20000],
+        }),
+    );
+    let buf = frame(&chp.to_handshake_bytes()?, 16384);
+
+    let mut parser = Parser::new();
+    match parser.parse_bytes(&buf) {
+        Ok(Some(x)) => assert_eq!(x, chp),
+        x => panic!("{x:?}"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid() -> Result<(), Box<dyn std::error::Error>> {
+    let handshake = rustls_client_hello_handshake()?;
+
+    // A record of another content type before the message is complete.
+    let mut buf = frame(&handshake[..10], 10);
+    buf.extend_from_slice(&[0x15, 0x03, 0x03, 0x00, 0x02, 0x02, 0x28]);
+    match read_client_hello_message(&buf) {
+        Err(ParseError::NotHandshake {
+            content_type: ContentType::Alert,
+            offset: 15,
+        }) => {}
+        x => panic!("{x:?}"),
+    }
+
+    // Another handshake message after the ClientHello in the same record.
+    let mut handshake_with_trailing = handshake.clone();
+    handshake_with_trailing.extend_from_slice(&[0x01, 0x00, 0x00, 0x00]);
+    let buf = frame(&handshake_with_trailing, handshake_with_trailing.len());
+    assert!(read_client_hello_message(&buf).is_err());
+
+    // Records after the ClientHello are left alone.
+    let mut buf = frame(&handshake, 100);
+    let len = buf.len();
+    buf.extend_from_slice(&[0x14, 0x03, 0x03, 0x00, 0x01, 0x01]);
+    let msg = read_client_hello_message(&buf)?.ok_or("read_client_hello_message none")?;
+    assert_eq!(msg.len, len);
+
+    Ok(())
+}
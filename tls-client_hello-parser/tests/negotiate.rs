@@ -0,0 +1,176 @@
+#![cfg(all(feature = "std", feature = "rustls"))]
+
+mod common;
+
+use std::sync::Arc;
+
+use tls_client_hello_parser::{
+    enums::{AlertDescription, CipherSuite, ExtensionType, NamedGroup, ProtocolVersion},
+    handshake::{ClientExtension, UnknownExtension},
+    negotiate::{Negotiated, Rejection},
+    Negotiation,
+};
+use tls_mkcert_test::rustls::make_server_config;
+
+use common::{rustls_client_hello, rustls_resumption_client_hello, tls12_client};
+
+#[test]
+fn test_rustls() -> Result<(), Box<dyn std::error::Error>> {
+    let chp = rustls_client_hello()?;
+    let config = Arc::new(make_server_config()?);
+
+    let negotiation = chp.negotiate_rustls(config.clone());
+    assert_eq!(
+        negotiation,
+        Negotiation::Accepted(Negotiated {
+            version: ProtocolVersion::TLSv1_3,
+            cipher_suite: CipherSuite::TLS13_AES_256_GCM_SHA384,
+            group: Some(NamedGroup::X25519),
+            alpn: None,
+        })
+    );
+    assert!(negotiation.is_accepted());
+    assert_eq!(chp.client_hello().negotiate_rustls(config), negotiation);
+
+    Ok(())
+}
+
+#[test]
+fn test_alpn() -> Result<(), Box<dyn std::error::Error>> {
+    let mut chp = rustls_client_hello()?;
+    let mut config = make_server_config()?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    let config = Arc::new(config);
+
+    chp.set_alpn([&b"http/1.1"[..], b"h2"])?;
+    match chp.negotiate_rustls(config.clone()) {
+        Negotiation::Accepted(negotiated) => {
+            assert_eq!(negotiated.alpn.as_deref(), Some(&b"h2"[..]))
+        }
+        x => panic!("{x:?}"),
+    }
+
+    let mut ignore_client_order = (*config).clone();
+    ignore_client_order.ignore_client_order = true;
+    match chp.negotiate_rustls(Arc::new(ignore_client_order)) {
+        Negotiation::Accepted(negotiated) => {
+            assert_eq!(negotiated.alpn.as_deref(), Some(&b"h2"[..]))
+        }
+        x => panic!("{x:?}"),
+    }
+
+    chp.set_alpn([&b"spdy/3"[..]])?;
+    match chp.negotiate_rustls(config) {
+        Negotiation::Rejected(Rejection { alert, .. }) => {
+            assert_eq!(alert, Some(AlertDescription::NoApplicationProtocol))
+        }
+        x => panic!("{x:?}"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_tls12() -> Result<(), Box<dyn std::error::Error>> {
+    let config = Arc::new(make_server_config()?);
+
+    assert_eq!(
+        tls12_client().build().negotiate_rustls(config.clone()),
+        Negotiation::Accepted(Negotiated {
+            version: ProtocolVersion::TLSv1_2,
+            cipher_suite: CipherSuite::TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256,
+            group: Some(NamedGroup::secp256r1),
+            alpn: None,
+        })
+    );
+
+    // No common cipher suite.
+    match tls12_client()
+        .with_cipher_suites([CipherSuite::TLS_RSA_WITH_AES_128_GCM_SHA256])
+        .build()
+        .negotiate_rustls(config.clone())
+    {
+        Negotiation::Rejected(Rejection { alert, .. }) => {
+            assert_eq!(alert, Some(AlertDescription::HandshakeFailure))
+        }
+        x => panic!("{x:?}"),
+    }
+
+    // TLS 1.1
+    match tls12_client()
+        .with_client_version(ProtocolVersion::TLSv1_1)
+        .build()
+        .negotiate_rustls(config)
+    {
+        Negotiation::Rejected(Rejection { error, alert }) => {
+            assert!(matches!(error, rustls::Error::PeerIncompatibleError(_)));
+            assert_eq!(alert, Some(AlertDescription::ProtocolVersion));
+        }
+        x => panic!("{x:?}"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_hello_retry_request() -> Result<(), Box<dyn std::error::Error>> {
+    let mut chp = rustls_client_hello()?;
+    assert!(chp.remove_extension(ExtensionType::KeyShare));
+    chp.extensions.insert(0, ClientExtension::KeyShare(vec![]));
+
+    assert_eq!(
+        chp.negotiate_rustls(Arc::new(make_server_config()?)),
+        Negotiation::HelloRetryRequest {
+            cipher_suite: CipherSuite::TLS13_AES_256_GCM_SHA384,
+            group: NamedGroup::X25519,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_too_large() -> Result<(), Box<dyn std::error::Error>> {
+    let padding = ClientExtension::Unknown(UnknownExtension {
+        typ: ExtensionType::Padding,
+        payload: vec![0; 0xffff],
+    });
+    let chp = tls12_client().with_extension(padding).build();
+
+    match chp.negotiate_rustls(Arc::new(make_server_config()?)) {
+        Negotiation::Rejected(Rejection { alert, .. }) => {
+            assert_eq!(alert, Some(AlertDescription::DecodeError))
+        }
+        x => panic!("{x:?}"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_resumption() -> Result<(), Box<dyn std::error::Error>> {
+    let config = Arc::new(make_server_config()?);
+    let chp = rustls_resumption_client_hello(&config)?;
+    assert!(chp.find_extension(ExtensionType::PreSharedKey).is_some());
+
+    let client_hello = chp.client_hello();
+    assert_eq!(client_hello.random(), &chp.random.0);
+    assert_eq!(client_hello.session_id(), chp.session_id.as_ref());
+
+    // Encoded again without pre_shared_key, a full handshake that leaves the session stored.
+    assert!(client_hello.negotiate_rustls(config.clone()).is_accepted());
+
+    // The server still has the session and checks the binders, which fail once the ClientHello
+    // is changed.
+    let mut stale = chp;
+    stale.random.0[0] ^= 1;
+    assert_eq!(
+        stale.negotiate_rustls(config),
+        Negotiation::Rejected(Rejection {
+            error: rustls::Error::PeerMisbehavedError("client sent wrong binder".into()),
+            alert: Some(AlertDescription::DecryptError),
+        })
+    );
+
+    Ok(())
+}
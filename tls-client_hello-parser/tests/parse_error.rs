@@ -0,0 +1,180 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use tls_client_hello_parser::{
+    codec::InvalidMessage,
+    enums::{ContentType, ExtensionType, HandshakeType},
+    ParseError, Parser, ParserConfig,
+};
+
+use common::{client_hello_body_raw, frame, handshake_message};
+
+fn parse_err(buf: &[u8]) -> ParseError {
+    let config = ParserConfig {
+        reject_duplicate_extensions: true,
+        ..Default::default()
+    };
+    match Parser::with_config(config).parse_bytes(buf) {
+        Err(err) => err,
+        x => panic!("{x:?}"),
+    }
+}
+
+#[test]
+fn test_kinds() {
+    let err = parse_err(b"\x17\x03\x03\x00\x01\x00");
+    assert!(matches!(
+        err,
+        ParseError::NotHandshake {
+            content_type: ContentType::ApplicationData,
+            offset: 0
+        }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "record at offset 0 has content type ApplicationData, expected Handshake"
+    );
+
+    let err = parse_err(b"\x16\x03\x03\x00\x04\x02\x00\x00\x00");
+    assert!(matches!(
+        err,
+        ParseError::NotClientHello {
+            handshake_type: HandshakeType::ServerHello,
+            offset: 5
+        }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "handshake message at offset 5 is ServerHello, expected ClientHello"
+    );
+
+    let err = parse_err(b"\x16\x03\x01\x40\x01");
+    assert!(matches!(
+        err,
+        ParseError::RecordTooLarge {
+            len: 16385,
+            offset: 0
+        }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "record at offset 0 is 16385 bytes long, the limit is 16384"
+    );
+
+    // signature_algorithms claims 5 bytes, only 1 is left.
+    let err = parse_err(&frame(
+        &handshake_message(&client_hello_body_raw(
+            0x0303,
+            &[0x1301],
+            Some(&[0x00, 0x0d, 0x00, 0x05, 0x00]),
+        )),
+        usize::MAX,
+    ));
+    assert!(matches!(
+        err,
+        ParseError::TruncatedExtension {
+            ext_type: ExtensionType::SignatureAlgorithms,
+            offset: 52
+        }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "extension SignatureAlgorithms at offset 52 is truncated"
+    );
+
+    let err = parse_err(&frame(
+        &handshake_message(&client_hello_body_raw(
+            0x0303,
+            &[0x1301],
+            Some(&[0x00, 0x0d, 0x00, 0x03, 0x00, 0x04, 0x04]),
+        )),
+        usize::MAX,
+    ));
+    assert!(matches!(
+        err,
+        ParseError::MalformedExtension {
+            ext_type: ExtensionType::SignatureAlgorithms,
+            err: InvalidMessage::MissingData(_),
+            offset: 52
+        }
+    ));
+    assert_eq!(err.offset(), Some(52));
+
+    let err = parse_err(&frame(
+        &handshake_message(&client_hello_body_raw(
+            0x0303,
+            &[0x1301],
+            Some(&[0x00, 0x17, 0x00, 0x00, 0x00, 0x17, 0x00, 0x00]),
+        )),
+        usize::MAX,
+    ));
+    assert!(matches!(
+        err,
+        ParseError::DuplicateExtension {
+            ext_type: ExtensionType::ExtendedMasterSecret,
+            offset: 56
+        }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "extension ExtendedMasterSecret at offset 56 was already sent"
+    );
+
+    let err = parse_err(b"GET / HTTP/1.1\r\n");
+    assert!(matches!(
+        err,
+        ParseError::InvalidMessage {
+            err: InvalidMessage::InvalidContentType,
+            offset: 0
+        }
+    ));
+}
+
+#[test]
+fn test_offset_across_records() {
+    // The duplicate is at offset 51 of the handshake message, in the third 20 bytes fragment.
+    let handshake = handshake_message(&client_hello_body_raw(
+        0x0303,
+        &[0x1301],
+        Some(&[0x00, 0x17, 0x00, 0x00, 0x00, 0x17, 0x00, 0x00]),
+    ));
+    let buf = frame(&handshake, 20);
+    assert_eq!(&buf[66..68], &[0x00, 0x17]);
+
+    assert!(matches!(
+        parse_err(&buf),
+        ParseError::DuplicateExtension {
+            ext_type: ExtensionType::ExtendedMasterSecret,
+            offset: 66
+        }
+    ));
+
+    // The extension type itself is split by a record boundary.
+    let buf = frame(&handshake, 2);
+    let err = parse_err(&buf);
+    let offset = err.offset().expect("offset");
+    assert_eq!((buf[offset], buf[offset + 1 + 5]), (0x00, 0x17));
+    assert_eq!(offset, 5 + 51 / 2 * 7 + 51 % 2);
+}
+
+#[test]
+fn test_duplicate_extensions_allowed_by_default() -> Result<(), ParseError> {
+    let handshake = handshake_message(&client_hello_body_raw(
+        0x0303,
+        &[0x1301],
+        Some(&[0x00, 0x17, 0x00, 0x00, 0x00, 0x17, 0x00, 0x00]),
+    ));
+    let chp = Parser::new()
+        .parse_bytes(&frame(&handshake, 20))?
+        .expect("ClientHello");
+
+    let client_hello = chp.client_hello();
+    assert_eq!(
+        client_hello.duplicate_extensions(),
+        vec![ExtensionType::ExtendedMasterSecret]
+    );
+    assert!(client_hello.validate_for_rustls().is_err());
+
+    Ok(())
+}
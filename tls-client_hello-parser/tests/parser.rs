@@ -1,3 +1,5 @@
+#![cfg(feature = "std")]
+
 use std::{
     io::{Cursor, ErrorKind as IoErrorKind, Write as _},
     sync::Arc,
@@ -5,10 +7,14 @@ use std::{
 
 use rustls::{
     cipher_suite::TLS13_CHACHA20_POLY1305_SHA256, version::TLS13, ClientConfig, ClientConnection,
-    ProtocolVersion, RootCertStore,
+    RootCertStore,
 };
 
-use tls_client_hello_parser::{ParseError, Parser};
+use tls_client_hello_parser::{
+    codec::InvalidMessage,
+    enums::{CipherSuite, Compression, ContentType, HandshakeType, ProtocolVersion},
+    ParseError, Parser,
+};
 
 #[test]
 fn test_parse() -> Result<(), Box<dyn std::error::Error>> {
@@ -68,7 +74,7 @@ fn test_parse() -> Result<(), Box<dyn std::error::Error>> {
 
         match parser.parse(&mut cursor) {
             Ok(Some(chp)) => {
-                assert_eq!(chp.client_hello()?.server_name(), Some("example.com"));
+                assert_eq!(chp.client_hello().server_name(), Some("example.com"));
             }
             x => panic!("{x:?}"),
         }
@@ -93,11 +99,21 @@ fn test_parse() -> Result<(), Box<dyn std::error::Error>> {
 
         match parser.parse(&mut cursor) {
             Ok(Some(chp)) => {
+                assert_eq!(chp.client_version, ProtocolVersion::TLSv1_2);
+                assert_eq!(
+                    chp.cipher_suites,
+                    vec![
+                        CipherSuite::TLS13_CHACHA20_POLY1305_SHA256,
+                        CipherSuite::TLS_EMPTY_RENEGOTIATION_INFO_SCSV
+                    ]
+                );
+                assert_eq!(chp.compression_methods, vec![Compression::Null]);
+                assert_eq!(chp.session_id.len(), 32);
                 assert_eq!(
                     chp.get_versions_extension(),
-                    Some(&vec![ProtocolVersion::TLSv1_3])
+                    Some(&[ProtocolVersion::TLSv1_3][..])
                 );
-                assert_eq!(chp.client_hello()?.server_name(), Some("xxx.com"));
+                assert_eq!(chp.client_hello().server_name(), Some("xxx.com"));
             }
             x => panic!("{x:?}"),
         }
@@ -105,3 +121,102 @@ fn test_parse() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn test_parse_bytes() -> Result<(), Box<dyn std::error::Error>> {
+    let mut parser = Parser::new();
+
+    let root_store = RootCertStore::empty();
+    let client_config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(root_store)
+        .with_no_client_auth();
+    let client_config = Arc::new(client_config);
+    let mut client = ClientConnection::new(client_config, "example.com".try_into()?)?;
+    let mut buf = Vec::new();
+    client.write_tls(&mut buf)?;
+
+    let (buf, buf_last_byte) = buf.split_at(buf.len() - 1);
+    for bytes in buf.chunks(10) {
+        match parser.parse_bytes(bytes) {
+            Ok(None) => {}
+            x => panic!("{x:?}"),
+        }
+    }
+
+    match parser.parse_bytes(buf_last_byte) {
+        Ok(Some(chp)) => {
+            assert_eq!(chp.client_hello().server_name(), Some("example.com"));
+        }
+        x => panic!("{x:?}"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_invalid() {
+    {
+        let mut parser = Parser::new();
+
+        match parser.parse(&mut Cursor::new(b"\x17\x03\x03\x00\x01\x00")) {
+            Err(ParseError::NotHandshake {
+                content_type: ContentType::ApplicationData,
+                offset: 0,
+            }) => {}
+            x => panic!("{x:?}"),
+        }
+    }
+
+    {
+        let mut parser = Parser::new();
+
+        match parser.parse(&mut Cursor::new(b"\x16\x03\x03\x00\x04\x02\x00\x00\x00")) {
+            Err(ParseError::NotClientHello {
+                handshake_type: HandshakeType::ServerHello,
+                offset: 5,
+            }) => {}
+            x => panic!("{x:?}"),
+        }
+    }
+
+    {
+        let mut parser = Parser::new();
+
+        match parser.parse(&mut Cursor::new(b"GET / HTTP/1.1\r\n")) {
+            Err(ParseError::InvalidMessage {
+                err: InvalidMessage::InvalidContentType,
+                offset: 0,
+            }) => {}
+            x => panic!("{x:?}"),
+        }
+    }
+
+    {
+        let mut parser = Parser::new();
+
+        match parser.parse(&mut Cursor::new(
+            b"\x16\x03\x03\x00\x05\x01\x00\x00\x01\x03",
+        )) {
+            Err(ParseError::InvalidMessage {
+                err: InvalidMessage::MissingData(_),
+                offset: 9,
+            }) => {}
+            x => panic!("{x:?}"),
+        }
+    }
+
+    {
+        let mut parser = Parser::new();
+
+        let mut cursor = Cursor::new(b"\x16\x03\x03\x00\x04\x01\x00\x00\x01");
+        match parser.parse(&mut cursor) {
+            Ok(None) => {}
+            x => panic!("{x:?}"),
+        }
+        match parser.parse(&mut cursor) {
+            Err(ParseError::IoError(err)) if err.kind() == IoErrorKind::UnexpectedEof => {}
+            x => panic!("{x:?}"),
+        }
+    }
+}
@@ -0,0 +1,173 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use tls_client_hello_parser::{Limit, ParseError, Parser, ParserConfig, Progress};
+use tls_mkcert_test::SNI;
+
+use common::{
+    frame, handshake_message, minimal_client_hello_body, rustls_client_hello_record, server_name,
+};
+
+fn limit_exceeded(config: ParserConfig, buf: &[u8]) -> (Limit, usize) {
+    match Parser::with_config(config).feed(buf) {
+        Err(ParseError::LimitExceeded { limit, offset }) => (limit, offset),
+        x => panic!("{x:?}"),
+    }
+}
+
+#[test]
+fn test_default() -> Result<(), Box<dyn std::error::Error>> {
+    let buf = rustls_client_hello_record()?;
+
+    match Parser::with_config(ParserConfig::default()).feed(&buf)? {
+        Progress::Done(chp, _) => assert_eq!(chp.client_hello().server_name(), Some(SNI)),
+        x => panic!("{x:?}"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_limits() {
+    let config = ParserConfig::default();
+
+    // Only the record and handshake headers are needed.
+    assert_eq!(
+        limit_exceeded(
+            ParserConfig {
+                max_client_hello_size: 1000,
+                ..config
+            },
+            &[0x16, 0x03, 0x01, 0x40, 0x00, 0x01, 0x00, 0x03, 0xe9]
+        ),
+        (Limit::ClientHelloSize, 6)
+    );
+    assert!(matches!(
+        Parser::with_config(ParserConfig {
+            max_client_hello_size: 1001,
+            ..config
+        })
+        .feed(&[0x16, 0x03, 0x01, 0x40, 0x00, 0x01, 0x00, 0x03, 0xe9]),
+        Ok(Progress::NeedMore { .. })
+    ));
+
+    // Fails on the first byte of the fourth record.
+    let buf = frame(&handshake_message(&minimal_client_hello_body(&[])), 1);
+    assert_eq!(
+        limit_exceeded(
+            ParserConfig {
+                max_records: 3,
+                ..config
+            },
+            &buf[..3 * 6 + 1]
+        ),
+        (Limit::Records, 18)
+    );
+
+    let buf = frame(
+        &handshake_message(&minimal_client_hello_body(&[
+            (0x0017, vec![]),
+            (0x0023, vec![]),
+            (0x0016, vec![]),
+        ])),
+        usize::MAX,
+    );
+    assert_eq!(
+        limit_exceeded(
+            ParserConfig {
+                max_extensions: 2,
+                ..config
+            },
+            &buf
+        ),
+        (Limit::Extensions, 60)
+    );
+    assert!(Parser::with_config(ParserConfig {
+        max_extensions: 3,
+        ..config
+    })
+    .feed(&buf)
+    .is_ok());
+
+    assert_eq!(
+        limit_exceeded(
+            ParserConfig {
+                max_cipher_suites: 0,
+                ..config
+            },
+            &frame(
+                &handshake_message(&minimal_client_hello_body(&[])),
+                usize::MAX
+            )
+        ),
+        (Limit::CipherSuites, 46)
+    );
+
+    let buf = frame(
+        &handshake_message(&minimal_client_hello_body(&[(
+            0x0000,
+            server_name(&[(0, b"example.com")]),
+        )])),
+        usize::MAX,
+    );
+    assert_eq!(
+        limit_exceeded(
+            ParserConfig {
+                max_server_name_len: 10,
+                ..config
+            },
+            &buf
+        ),
+        (Limit::ServerNameLen, 52)
+    );
+    assert!(Parser::with_config(ParserConfig {
+        max_server_name_len: 11,
+        ..config
+    })
+    .feed(&buf)
+    .is_ok());
+}
+
+#[test]
+fn test_trailing_input() -> Result<(), Box<dyn std::error::Error>> {
+    let config = ParserConfig {
+        max_records: 3,
+        ..ParserConfig::default()
+    };
+    let junk = vec![0x17; 1 << 20];
+
+    // Only what the records may need is buffered, whatever follows them.
+    let mut buf = rustls_client_hello_record()?;
+    let len = buf.len();
+    buf.extend_from_slice(&junk);
+    match Parser::with_config(config).feed(&buf)? {
+        Progress::Done(chp, n) => {
+            assert_eq!(n, len);
+            assert_eq!(chp.client_hello().server_name(), Some(SNI));
+        }
+        x => panic!("{x:?}"),
+    }
+
+    let mut buf = frame(&handshake_message(&minimal_client_hello_body(&[])), 1);
+    buf.truncate(3 * 6);
+    buf.extend_from_slice(&junk);
+    assert_eq!(limit_exceeded(config, &buf), (Limit::Records, 18));
+
+    let mut parser = Parser::with_config(config);
+    assert!(matches!(
+        parser.feed(&buf[..3 * 6]),
+        Ok(Progress::NeedMore { .. })
+    ));
+    for _ in 0..4 {
+        assert!(matches!(
+            parser.feed(&junk),
+            Err(ParseError::LimitExceeded {
+                limit: Limit::Records,
+                offset: 18
+            })
+        ));
+    }
+
+    Ok(())
+}
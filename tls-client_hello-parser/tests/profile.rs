@@ -0,0 +1,234 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use tls_client_hello_parser::{
+    enums::{CipherSuite, NamedGroup, ProtocolVersion, SignatureScheme},
+    handshake::ClientExtension,
+    profile::{Evaluation, INTERMEDIATE, MODERN, OLD},
+    ClientHelloBuilder,
+};
+
+use common::{rustls_client_hello, tls12_client};
+
+#[test]
+fn test_rustls() -> Result<(), Box<dyn std::error::Error>> {
+    let chp = rustls_client_hello()?;
+    for profile in [&MODERN, &INTERMEDIATE, &OLD] {
+        let evaluation = chp.client_hello().evaluate(profile);
+        assert!(
+            evaluation.is_compatible(),
+            "{}: {evaluation:?}",
+            profile.name
+        );
+        assert_eq!(evaluation, Evaluation::default());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_tls12_client() {
+    let chp = tls12_client().build();
+    let client_hello = chp.client_hello();
+
+    assert!(INTERMEDIATE.evaluate(&client_hello).is_compatible());
+    assert!(OLD.evaluate(&client_hello).is_compatible());
+    assert_eq!(
+        MODERN.evaluate(&client_hello),
+        Evaluation {
+            missing_versions: vec![ProtocolVersion::TLSv1_3],
+            missing_cipher_suites: MODERN.cipher_suites.to_vec(),
+            ..Default::default()
+        }
+    );
+
+    // CBC only.
+    let chp = tls12_client()
+        .with_cipher_suites([CipherSuite::TLS_ECDHE_RSA_WITH_AES_128_CBC_SHA])
+        .build();
+    let evaluation = INTERMEDIATE.evaluate(&chp.client_hello());
+    // The TLS 1.2 ones, TLS 1.3 isn't offered.
+    assert_eq!(
+        evaluation.missing_cipher_suites,
+        &INTERMEDIATE.cipher_suites[3..]
+    );
+    assert!(evaluation.missing_versions.is_empty());
+    assert!(OLD.evaluate(&chp.client_hello()).is_compatible());
+}
+
+#[test]
+fn test_legacy_client() {
+    // TLS 1.0 without extensions.
+    let chp = ClientHelloBuilder::new()
+        .with_client_version(ProtocolVersion::TLSv1_0)
+        .with_cipher_suites([CipherSuite::TLS_RSA_WITH_AES_128_CBC_SHA])
+        .build();
+    let client_hello = chp.client_hello();
+
+    assert!(OLD.evaluate(&client_hello).is_compatible());
+    let evaluation = INTERMEDIATE.evaluate(&client_hello);
+    assert_eq!(
+        evaluation.missing_versions,
+        [ProtocolVersion::TLSv1_3, ProtocolVersion::TLSv1_2]
+    );
+    assert_eq!(evaluation.missing_cipher_suites, INTERMEDIATE.cipher_suites);
+    assert!(!evaluation.is_compatible());
+
+    // No supported_versions, TLS 1.2 at most whatever the legacy version says.
+    let chp = tls12_client()
+        .with_client_version(ProtocolVersion::TLSv1_3)
+        .build();
+    assert_eq!(
+        MODERN.evaluate(&chp.client_hello()).missing_versions,
+        [ProtocolVersion::TLSv1_3]
+    );
+}
+
+#[test]
+fn test_missing_groups_and_signature_schemes() {
+    let tls13_client = || {
+        ClientHelloBuilder::new()
+            .with_cipher_suites([CipherSuite::TLS13_AES_128_GCM_SHA256])
+            .with_extension(ClientExtension::SupportedVersions(vec![
+                ProtocolVersion::TLSv1_3,
+            ]))
+    };
+
+    let chp = tls13_client()
+        .with_extension(ClientExtension::NamedGroups(vec![NamedGroup::X448]))
+        .with_extension(ClientExtension::SignatureAlgorithms(vec![
+            SignatureScheme::RSA_PSS_SHA256,
+            SignatureScheme::RSA_PKCS1_SHA256,
+        ]))
+        .build();
+    assert_eq!(
+        MODERN.evaluate(&chp.client_hello()),
+        Evaluation {
+            missing_groups: MODERN.groups.to_vec(),
+            missing_signature_schemes: MODERN.signature_schemes.to_vec(),
+            ..Default::default()
+        }
+    );
+    // PKCS#1 doesn't count with TLS 1.3.
+    assert_eq!(
+        INTERMEDIATE.evaluate(&chp.client_hello()),
+        Evaluation {
+            missing_groups: INTERMEDIATE.groups.to_vec(),
+            ..Default::default()
+        }
+    );
+    let chp = tls13_client()
+        .with_extension(ClientExtension::NamedGroups(vec![NamedGroup::X25519]))
+        .with_extension(ClientExtension::SignatureAlgorithms(vec![
+            SignatureScheme::RSA_PKCS1_SHA256,
+        ]))
+        .build();
+    assert_eq!(
+        INTERMEDIATE
+            .evaluate(&chp.client_hello())
+            .missing_signature_schemes,
+        [
+            SignatureScheme::ECDSA_NISTP256_SHA256,
+            SignatureScheme::ECDSA_NISTP384_SHA384,
+            SignatureScheme::ECDSA_NISTP521_SHA512,
+            SignatureScheme::RSA_PSS_SHA256,
+            SignatureScheme::RSA_PSS_SHA384,
+            SignatureScheme::RSA_PSS_SHA512,
+        ]
+    );
+
+    // TLS 1.2 without signature_algorithms means SHA-1, which only old allows.
+    let chp = tls12_client()
+        .with_extensions([ClientExtension::NamedGroups(vec![NamedGroup::X25519])])
+        .build();
+    assert_eq!(
+        INTERMEDIATE
+            .evaluate(&chp.client_hello())
+            .missing_signature_schemes,
+        // The RSA ones, for the ECDHE_RSA suites.
+        &INTERMEDIATE.signature_schemes[3..]
+    );
+    assert!(OLD.evaluate(&chp.client_hello()).is_compatible());
+
+    // DHE doesn't need a group, ECDHE without supported_groups can use any curve.
+    let chp = tls12_client()
+        .with_cipher_suites([CipherSuite::TLS_DHE_RSA_WITH_AES_128_GCM_SHA256])
+        .with_extensions([ClientExtension::SignatureAlgorithms(vec![
+            SignatureScheme::RSA_PSS_SHA256,
+        ])])
+        .build();
+    assert!(INTERMEDIATE.evaluate(&chp.client_hello()).is_compatible());
+    let chp = tls12_client()
+        .with_extensions([ClientExtension::SignatureAlgorithms(vec![
+            SignatureScheme::RSA_PSS_SHA256,
+        ])])
+        .build();
+    assert!(INTERMEDIATE.evaluate(&chp.client_hello()).is_compatible());
+    let chp = tls12_client()
+        .with_extensions([
+            ClientExtension::NamedGroups(vec![NamedGroup::X448]),
+            ClientExtension::SignatureAlgorithms(vec![SignatureScheme::RSA_PSS_SHA256]),
+        ])
+        .build();
+    assert_eq!(
+        INTERMEDIATE.evaluate(&chp.client_hello()).missing_groups,
+        INTERMEDIATE.groups
+    );
+}
+
+#[test]
+fn test_signature_schemes_by_suite() {
+    // ECDHE_RSA suites, but the client only verifies ECDSA signatures.
+    let chp = tls12_client()
+        .with_extensions([
+            ClientExtension::NamedGroups(vec![NamedGroup::secp256r1]),
+            ClientExtension::SignatureAlgorithms(vec![SignatureScheme::ECDSA_NISTP256_SHA256]),
+        ])
+        .build();
+    let evaluation = INTERMEDIATE.evaluate(&chp.client_hello());
+    assert!(!evaluation.is_compatible());
+    assert_eq!(
+        evaluation,
+        Evaluation {
+            missing_signature_schemes: INTERMEDIATE.signature_schemes[3..].to_vec(),
+            ..Default::default()
+        }
+    );
+
+    // With an ECDHE_ECDSA suite too, intermediate has ECDSA certificates.
+    let chp = tls12_client()
+        .with_cipher_suites([
+            CipherSuite::TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256,
+            CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256,
+        ])
+        .with_extensions([
+            ClientExtension::NamedGroups(vec![NamedGroup::secp256r1]),
+            ClientExtension::SignatureAlgorithms(vec![SignatureScheme::ECDSA_NISTP256_SHA256]),
+        ])
+        .build();
+    assert!(INTERMEDIATE.evaluate(&chp.client_hello()).is_compatible());
+
+    // Old only has RSA certificates, and so no ECDHE_ECDSA suites.
+    let chp = tls12_client()
+        .with_cipher_suites([
+            CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256,
+            CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_128_CBC_SHA,
+        ])
+        .build();
+    // Intermediate's ECDSA certificate can't sign with the RSA schemes of the client.
+    assert_eq!(
+        INTERMEDIATE
+            .evaluate(&chp.client_hello())
+            .missing_signature_schemes,
+        &INTERMEDIATE.signature_schemes[..3]
+    );
+    let evaluation = OLD.evaluate(&chp.client_hello());
+    assert!(!evaluation.is_compatible());
+    assert!(!evaluation
+        .missing_cipher_suites
+        .contains(&CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256));
+    assert!(evaluation
+        .missing_cipher_suites
+        .contains(&CipherSuite::TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256));
+}
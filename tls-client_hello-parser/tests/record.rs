@@ -0,0 +1,49 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use tls_client_hello_parser::record::is_not_tls;
+
+use common::rustls_client_hello_record;
+
+#[test]
+fn test_is_not_tls() -> Result<(), Box<dyn std::error::Error>> {
+    let buf = rustls_client_hello_record()?;
+    for i in 0..=buf.len() {
+        assert!(!is_not_tls(&buf[..i]));
+    }
+
+    for prefix in [
+        &b""[..],
+        b"\x16",
+        b"\x16\x03",
+        b"\x16\x03\x00",
+        b"\x16\x03\x04",
+        b"\x16\x03\x01\x40",
+        b"\x16\x03\x01\x40\x00",
+        b"\x16\x03\x03\x00\x01",
+    ] {
+        assert!(!is_not_tls(prefix), "{prefix:?}");
+    }
+
+    for prefix in [
+        // HTTP, SSH and TLS records that can't carry a ClientHello.
+        &b"G"[..],
+        b"SSH-2.0-OpenSSH_9.6\r\n",
+        b"\x15\x03\x03\x00\x02",
+        b"\x17\x03\x03\x00\x10",
+        // SSL 2.0 and unknown versions.
+        b"\x80",
+        b"\x16\x02",
+        b"\x16\x03\x05",
+        b"\x16\xfe\xfd",
+        // Empty and oversized records.
+        b"\x16\x03\x01\x00\x00",
+        b"\x16\x03\x01\x41",
+        b"\x16\x03\x01\x40\x01",
+    ] {
+        assert!(is_not_tls(prefix), "{prefix:?}");
+    }
+
+    Ok(())
+}
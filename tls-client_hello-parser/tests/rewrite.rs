@@ -0,0 +1,245 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use std::{io::Cursor, sync::Arc};
+
+use rustls::ServerConnection;
+
+use tls_client_hello_parser::{
+    codec::PayloadU8,
+    enums::{Compression, ExtensionType, ProtocolVersion},
+    handshake::{ClientExtension, UnknownExtension},
+    ClientHelloBuilder, ClientHelloPayload, RewriteError, ServerNameError,
+};
+use tls_mkcert_test::rustls::{make_server_config, make_server_connection};
+
+use common::{parse, rustls_client_hello_record, rustls_resumption_client_hello};
+
+fn extension_types(chp: &ClientHelloPayload) -> Vec<ExtensionType> {
+    chp.extensions.iter().map(|x| x.get_type()).collect()
+}
+
+fn unknown(typ: ExtensionType) -> ClientExtension {
+    ClientExtension::Unknown(UnknownExtension {
+        typ,
+        payload: vec![1, 2, 3],
+    })
+}
+
+#[test]
+fn test_rewrite_rustls() -> Result<(), Box<dyn std::error::Error>> {
+    let buf = rustls_client_hello_record()?;
+
+    let mut chp = parse(&buf);
+    let types = extension_types(&chp);
+    chp.set_server_name("Upstream.Example.COM")?;
+    chp.set_alpn([&b"h2"[..], b"http/1.1"])?;
+    assert_eq!(
+        extension_types(&chp),
+        [&types[..], &[ExtensionType::ALProtocolNegotiation]].concat()
+    );
+
+    let buf = chp.to_record_bytes(ProtocolVersion::TLSv1_0, 3)?;
+    let parsed = parse(&buf);
+    assert_eq!(parsed, chp);
+    assert_eq!(
+        parsed.client_hello().server_name(),
+        Some("upstream.example.com")
+    );
+
+    // The server accepts it.
+    let mut server = make_server_connection()?;
+    server.read_tls(&mut Cursor::new(&buf))?;
+    server.process_new_packets()?;
+    assert_eq!(server.sni_hostname(), Some("upstream.example.com"));
+
+    Ok(())
+}
+
+#[test]
+fn test_rewrite_rustls_resumption() -> Result<(), Box<dyn std::error::Error>> {
+    let server_config = Arc::new(make_server_config()?);
+    let chp = rustls_resumption_client_hello(&server_config)?;
+    assert_eq!(
+        extension_types(&chp).last(),
+        Some(&ExtensionType::PreSharedKey)
+    );
+
+    // Any change to the ClientHello invalidates the binders.
+    let mut stale = chp.clone();
+    stale.random.0[0] ^= 1;
+    let mut server = ServerConnection::new(server_config.clone())?;
+    server.read_tls(&mut Cursor::new(
+        &stale.to_record_bytes(ProtocolVersion::TLSv1_0, 1)?,
+    ))?;
+    assert_eq!(
+        server.process_new_packets().err(),
+        Some(rustls::Error::PeerMisbehavedError(
+            "client sent wrong binder".into()
+        ))
+    );
+
+    // Edits remove pre_shared_key, the server does a full handshake.
+    let mut edited = chp.clone();
+    edited.set_alpn([&b"http/1.1"[..]])?;
+    assert!(!extension_types(&edited).contains(&ExtensionType::PreSharedKey));
+    assert!(!extension_types(&edited).contains(&ExtensionType::EarlyData));
+    let mut server = ServerConnection::new(server_config)?;
+    server.read_tls(&mut Cursor::new(
+        &edited.to_record_bytes(ProtocolVersion::TLSv1_0, 1)?,
+    ))?;
+    server.process_new_packets()?;
+
+    // Removing nothing is not an edit.
+    let mut unchanged = chp.clone();
+    assert!(!unchanged.remove_extension(ExtensionType::Padding));
+    unchanged.retain_alpn(|_| false);
+    assert_eq!(unchanged, chp);
+
+    let mut removed = chp;
+    assert!(removed.remove_extension(ExtensionType::PreSharedKey));
+    assert!(!extension_types(&removed).contains(&ExtensionType::EarlyData));
+
+    Ok(())
+}
+
+#[test]
+fn test_server_name() -> Result<(), Box<dyn std::error::Error>> {
+    let mut chp = ClientHelloBuilder::new()
+        .with_extension(unknown(ExtensionType::ExtendedMasterSecret))
+        .build();
+
+    // Inserted first.
+    chp.set_server_name("example.com")?;
+    assert_eq!(
+        extension_types(&chp),
+        [
+            ExtensionType::ServerName,
+            ExtensionType::ExtendedMasterSecret
+        ]
+    );
+
+    // Replaced in place, with all nested lengths recomputed.
+    chp.extensions.swap(0, 1);
+    chp.set_server_name("a.example")?;
+    assert_eq!(
+        extension_types(&chp),
+        [
+            ExtensionType::ExtendedMasterSecret,
+            ExtensionType::ServerName
+        ]
+    );
+    let buf = chp.to_record_bytes(ProtocolVersion::TLSv1_2, 1)?;
+    let mut ext = vec![0x00, 0x00, 0x00, 14, 0x00, 12, 0x00, 0x00, 9];
+    ext.extend_from_slice(b"a.example");
+    assert!(buf.windows(ext.len()).any(|x| x == ext));
+    assert_eq!(parse(&buf).client_hello().server_name(), Some("a.example"));
+
+    assert_eq!(
+        chp.set_server_name("127.0.0.1"),
+        Err(RewriteError::InvalidServerName(ServerNameError::IpAddress))
+    );
+    assert!(chp.remove_server_name());
+    assert!(!chp.remove_server_name());
+    assert_eq!(extension_types(&chp), [ExtensionType::ExtendedMasterSecret]);
+
+    Ok(())
+}
+
+#[test]
+fn test_alpn() -> Result<(), Box<dyn std::error::Error>> {
+    let mut chp = ClientHelloBuilder::new()
+        .with_extension(unknown(ExtensionType::ExtendedMasterSecret))
+        .build();
+
+    // Inserted last.
+    chp.set_alpn([&b"\x0a\x0a"[..], b"h2", b"http/1.1"])?;
+    assert_eq!(
+        extension_types(&chp),
+        [
+            ExtensionType::ExtendedMasterSecret,
+            ExtensionType::ALProtocolNegotiation
+        ]
+    );
+
+    chp.retain_alpn(|x| x != b"h2");
+    assert_eq!(
+        chp.get_alpn_extension(),
+        Some(
+            &[
+                PayloadU8(b"\x0a\x0a".to_vec()),
+                PayloadU8(b"http/1.1".to_vec())
+            ][..]
+        )
+    );
+
+    // Reordered.
+    chp.set_alpn([&b"http/1.1"[..], b"\x0a\x0a"])?;
+    let parsed = parse(&chp.to_record_bytes(ProtocolVersion::TLSv1_0, 1)?);
+    assert_eq!(
+        parsed.client_hello().alpn().map(|x| x.collect::<Vec<_>>()),
+        Some(vec![&b"http/1.1"[..], b"\x0a\x0a"])
+    );
+
+    assert_eq!(
+        chp.set_alpn([&b""[..]]),
+        Err(RewriteError::InvalidAlpnProtocol)
+    );
+    assert_eq!(
+        chp.set_alpn([&[0; 256][..]]),
+        Err(RewriteError::InvalidAlpnProtocol)
+    );
+
+    chp.retain_alpn(|_| false);
+    assert_eq!(extension_types(&chp), [ExtensionType::ExtendedMasterSecret]);
+
+    chp.set_alpn([&b"h2"[..]])?;
+    chp.set_alpn([])?;
+    assert_eq!(extension_types(&chp), [ExtensionType::ExtendedMasterSecret]);
+
+    Ok(())
+}
+
+#[test]
+fn test_remove_extension_and_too_large() -> Result<(), Box<dyn std::error::Error>> {
+    let mut chp = ClientHelloBuilder::new()
+        .with_extension(unknown(ExtensionType::Padding))
+        .with_extension(unknown(ExtensionType::SessionTicket))
+        .with_extension(unknown(ExtensionType::Padding))
+        .build();
+
+    assert!(chp.remove_extension(ExtensionType::Padding));
+    assert_eq!(extension_types(&chp), [ExtensionType::SessionTicket]);
+
+    chp.extensions
+        .push(ClientExtension::Unknown(UnknownExtension {
+            typ: ExtensionType::Padding,
+            payload: vec![0; 65000],
+        }));
+    chp.extensions
+        .push(ClientExtension::Unknown(UnknownExtension {
+            typ: ExtensionType::Heartbeat,
+            payload: vec![0; 1000],
+        }));
+    assert!(matches!(
+        chp.to_record_bytes(ProtocolVersion::TLSv1_0, 1),
+        Err(RewriteError::MessageTooLarge(_))
+    ));
+
+    assert!(chp.remove_extension(ExtensionType::Heartbeat));
+    let buf = chp.to_record_bytes(ProtocolVersion::TLSv1_0, 1)?;
+    assert_eq!(parse(&buf), chp);
+
+    // Nested length prefixes are checked too.
+    chp.compression_methods = vec![Compression::Null; 256];
+    assert_eq!(
+        chp.to_record_bytes(ProtocolVersion::TLSv1_0, 1),
+        Err(RewriteError::FieldTooLarge {
+            field: "compression_methods",
+            len: 256
+        })
+    );
+
+    Ok(())
+}
@@ -0,0 +1,240 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use tls_client_hello_parser::{
+    enums::CipherSuite,
+    security::{
+        Authentication, BulkCipher, CipherSuiteInfo, CipherSuiteSummary, Classification, Grade,
+        KeyExchange, Mac,
+    },
+    ClientHelloBuilder,
+};
+
+use common::rustls_client_hello;
+
+fn info(value: u16) -> CipherSuiteInfo {
+    match CipherSuite::from(value).classify() {
+        Some(Classification::Classified(info)) => info,
+        x => panic!("{x:?}"),
+    }
+}
+
+#[test]
+fn test_classify() {
+    assert_eq!(
+        info(0xc02f),
+        CipherSuiteInfo {
+            key_exchange: KeyExchange::Ecdhe,
+            authentication: Authentication::Rsa,
+            cipher: BulkCipher::Aes,
+            key_bits: 128,
+            mac: Mac::Gcm,
+            export: false,
+        }
+    );
+    assert_eq!(info(0xc02f).grade(), Grade::Secure);
+
+    // TLS 1.3
+    let x = info(0x1303);
+    assert_eq!(
+        (
+            x.key_exchange,
+            x.authentication,
+            x.cipher,
+            x.key_bits,
+            x.mac
+        ),
+        (
+            KeyExchange::Any,
+            Authentication::Any,
+            BulkCipher::ChaCha20,
+            256,
+            Mac::Poly1305
+        )
+    );
+    assert_eq!(x.grade(), Grade::Secure);
+    assert_eq!(info(0x1305).mac, Mac::Ccm8);
+    let x = info(0xc0b4);
+    assert_eq!(
+        (x.cipher, x.mac, x.grade()),
+        (BulkCipher::Null, Mac::Sha256, Grade::Insecure)
+    );
+
+    // CBC, no forward secrecy.
+    let x = info(0x002f);
+    assert_eq!(
+        (
+            x.key_exchange,
+            x.cipher,
+            x.mac,
+            x.is_cbc(),
+            x.forward_secrecy()
+        ),
+        (KeyExchange::Rsa, BulkCipher::Aes, Mac::Sha1, true, false)
+    );
+    assert_eq!(x.grade(), Grade::Weak);
+    assert_eq!(info(0xc027).grade(), Grade::Weak);
+    assert_eq!(info(0x009c).grade(), Grade::Weak);
+    let x = info(0x000a);
+    assert_eq!(
+        (x.cipher, x.key_bits, x.grade()),
+        (BulkCipher::TripleDes, 168, Grade::Weak)
+    );
+
+    // Insecure
+    let x = info(0x0003);
+    assert_eq!(
+        (x.key_exchange, x.cipher, x.key_bits, x.mac, x.export),
+        (KeyExchange::Rsa, BulkCipher::Rc4, 40, Mac::Md5, true)
+    );
+    assert_eq!(x.grade(), Grade::Insecure);
+    let x = info(0x0029);
+    assert_eq!(
+        (x.key_exchange, x.cipher, x.key_bits, x.mac, x.export),
+        (KeyExchange::Krb5, BulkCipher::Des, 40, Mac::Md5, true)
+    );
+    let x = info(0x0000);
+    assert_eq!(
+        (x.key_exchange, x.authentication, x.cipher, x.mac),
+        (
+            KeyExchange::Null,
+            Authentication::None,
+            BulkCipher::Null,
+            Mac::Null
+        )
+    );
+    assert!(!x.is_anonymous());
+    let x = info(0xc018);
+    assert!(x.is_anonymous() && x.forward_secrecy());
+    assert_eq!(x.grade(), Grade::Insecure);
+    assert_eq!(info(0x0009).grade(), Grade::Insecure);
+
+    // PSK
+    let x = info(0xccad);
+    assert_eq!(
+        (x.key_exchange, x.authentication, x.grade()),
+        (KeyExchange::DhePsk, Authentication::Psk, Grade::Secure)
+    );
+    assert_eq!(info(0xc0aa).key_exchange, KeyExchange::DhePsk);
+    assert_eq!(info(0x00a8).grade(), Grade::Weak);
+
+    // Not suites, or not registered.
+    for x in [0x00ff, 0x5600, 0x0a0a, 0x001c, 0xff00, 0xc107] {
+        assert_eq!(CipherSuite::from(x).classify(), None);
+    }
+    assert!(CipherSuite::TLS_EMPTY_RENEGOTIATION_INFO_SCSV.is_signaling());
+    assert!(CipherSuite::from(0x5600).is_signaling());
+    assert!(!CipherSuite::TLS13_AES_128_GCM_SHA256.is_signaling());
+}
+
+#[test]
+fn test_classify_unclassified() {
+    // AEGIS, GOST and GOST with MGM.
+    for x in [0x1306, 0xc100, 0xc102, 0xc103, 0xc106] {
+        let cipher_suite = CipherSuite::from(x);
+        assert!(cipher_suite.iana().is_some());
+        assert_eq!(cipher_suite.classify(), Some(Classification::Unclassified));
+        assert_eq!(cipher_suite.classify().and_then(|x| x.info()), None);
+    }
+    assert_eq!(
+        CipherSuiteInfo::from_iana_name("TLS_GOSTR341112_256_WITH_MAGMA_CTR_OMAC"),
+        None
+    );
+}
+
+#[test]
+fn test_classify_registry() {
+    let mut unclassified = Vec::new();
+    for x in 0..=u16::MAX {
+        let cipher_suite = CipherSuite::from(x);
+        let Some(entry) = cipher_suite.iana() else {
+            continue;
+        };
+        match cipher_suite.classify() {
+            Some(Classification::Classified(_)) => {}
+            Some(Classification::Unclassified) => unclassified.push(entry.name),
+            None => assert!(
+                !entry.name.starts_with("TLS_") || cipher_suite.is_signaling(),
+                "{}",
+                entry.name
+            ),
+        }
+    }
+    assert_eq!(
+        unclassified,
+        [
+            "TLS_AEGIS_256_SHA512",
+            "TLS_GOSTR341112_256_WITH_KUZNYECHIK_CTR_OMAC",
+            "TLS_GOSTR341112_256_WITH_MAGMA_CTR_OMAC",
+            "TLS_GOSTR341112_256_WITH_28147_CNT_IMIT",
+            "TLS_GOSTR341112_256_WITH_KUZNYECHIK_MGM_L",
+            "TLS_GOSTR341112_256_WITH_MAGMA_MGM_L",
+            "TLS_GOSTR341112_256_WITH_KUZNYECHIK_MGM_S",
+            "TLS_GOSTR341112_256_WITH_MAGMA_MGM_S",
+        ]
+    );
+}
+
+#[test]
+fn test_summary() {
+    let suites = [
+        CipherSuite::Unknown(0x2a2a),
+        CipherSuite::TLS13_AES_128_GCM_SHA256,
+        CipherSuite::TLS_ECDHE_RSA_WITH_AES_128_CBC_SHA,
+        CipherSuite::TLS_RSA_WITH_AES_128_GCM_SHA256,
+        CipherSuite::Unknown(0x000a),
+        CipherSuite::Unknown(0x0005),
+        CipherSuite::Unknown(0x0019),
+        CipherSuite::Unknown(0x0002),
+        CipherSuite::Unknown(0x1306),
+        CipherSuite::TLS_EMPTY_RENEGOTIATION_INFO_SCSV,
+    ];
+    let chp = ClientHelloBuilder::new().with_cipher_suites(suites).build();
+
+    let summary = chp.client_hello().cipher_suite_summary();
+    assert_eq!(
+        summary,
+        CipherSuiteSummary {
+            suites: 8,
+            unknown: 0,
+            unclassified: 1,
+            null: 1,
+            anonymous: 1,
+            export: 1,
+            rc4: 1,
+            triple_des: 1,
+            cbc: 3,
+            aead: 2,
+            non_forward_secret: 4,
+            worst_grade: Some(Grade::Insecure),
+            best_grade: Some(Grade::Secure),
+        }
+    );
+    assert!(!summary.cbc_only());
+
+    let summary: CipherSuiteSummary = [
+        CipherSuite::TLS_ECDHE_RSA_WITH_AES_128_CBC_SHA,
+        CipherSuite::TLS_ECDHE_RSA_WITH_AES_256_CBC_SHA,
+    ]
+    .into_iter()
+    .collect();
+    assert!(summary.cbc_only());
+    assert_eq!(summary.worst_grade, Some(Grade::Weak));
+
+    assert_eq!(
+        CipherSuiteSummary::from_iter([]),
+        CipherSuiteSummary::default()
+    );
+}
+
+#[test]
+fn test_summary_rustls() -> Result<(), Box<dyn std::error::Error>> {
+    let chp = rustls_client_hello()?;
+    let summary = chp.client_hello().cipher_suite_summary();
+    assert_eq!(summary.suites, 9);
+    assert_eq!(summary.aead, 9);
+    assert_eq!(summary.worst_grade, Some(Grade::Secure));
+
+    Ok(())
+}
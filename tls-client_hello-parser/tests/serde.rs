@@ -0,0 +1,160 @@
+#![cfg(all(feature = "std", feature = "serde"))]
+
+mod common;
+
+use serde_json::{json, Value};
+use tls_client_hello_parser::{
+    enums::{CipherSuite, ExtensionType},
+    handshake::{ClientExtension, UnknownExtension},
+    schema::{ClientHelloDocument, DocumentError},
+    ClientHelloBuilder, ClientHelloPayload,
+};
+use tls_mkcert_test::SNI;
+
+use common::rustls_client_hello;
+
+#[test]
+fn test_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+    let mut chp = rustls_client_hello()?;
+    chp.set_alpn([&b"h2"[..], b"http/1.1"])?;
+
+    let value = serde_json::to_value(&chp)?;
+    assert_eq!(value["schema_version"], 1);
+    assert_eq!(
+        value["client_version"],
+        json!({"value": 0x0303, "name": "TLSv1_2"})
+    );
+    assert_eq!(value["random"].as_str().map(str::len), Some(64));
+    assert_eq!(
+        value["cipher_suites"][0],
+        json!({"value": 0x1302, "name": "TLS_AES_256_GCM_SHA384"})
+    );
+    assert_eq!(value["server_name"], SNI);
+    assert_eq!(value["alpn"], json!(["h2", "http/1.1"]));
+    assert_eq!(value["ja3_hash"], chp.ja3_hash());
+    assert!(value["ja4"]
+        .as_str()
+        .unwrap_or_default()
+        .starts_with("t13d"));
+
+    let sni = value["extensions"]
+        .as_array()
+        .and_then(|x| x.iter().find(|x| x["type"]["value"] == 0))
+        .ok_or("no server_name")?;
+    assert_eq!(sni["type"]["name"], "server_name");
+    assert!(sni["data"]
+        .as_str()
+        .unwrap_or_default()
+        .ends_with(&hex(SNI)));
+
+    let parsed: ClientHelloPayload = serde_json::from_value(value.clone())?;
+    assert_eq!(parsed, chp);
+
+    // Derived fields and names are ignored.
+    let mut value = value;
+    for key in ["server_name", "alpn", "ja3", "ja3_hash", "ja4", "ja4_r"] {
+        value.as_object_mut().ok_or("object")?.remove(key);
+    }
+    value["cipher_suites"][0]["name"] = Value::Null;
+    let parsed: ClientHelloPayload = serde_json::from_value(value)?;
+    assert_eq!(parsed, chp);
+
+    Ok(())
+}
+
+fn hex(s: &str) -> String {
+    s.bytes().map(|x| format!("{x:02x}")).collect()
+}
+
+#[test]
+fn test_names() -> Result<(), Box<dyn std::error::Error>> {
+    let chp = ClientHelloBuilder::new()
+        .with_cipher_suites([
+            CipherSuite::from(0x1a1a),
+            CipherSuite::from(0x1234),
+            CipherSuite::TLS_EMPTY_RENEGOTIATION_INFO_SCSV,
+        ])
+        .with_extension(ClientExtension::Unknown(UnknownExtension {
+            typ: ExtensionType::from(0x7a7b),
+            payload: vec![1, 2],
+        }))
+        .with_extension(ClientExtension::Unknown(UnknownExtension {
+            typ: ExtensionType::from(0x0028),
+            payload: vec![],
+        }))
+        .with_extension(ClientExtension::Unknown(UnknownExtension {
+            typ: ExtensionType::RenegotiationInfo,
+            payload: vec![0],
+        }))
+        .with_empty_extensions_block(true)
+        .build();
+
+    let value = serde_json::to_value(&chp)?;
+    assert_eq!(
+        value["cipher_suites"],
+        json!([
+            {"value": 0x1a1a, "name": "GREASE"},
+            {"value": 0x1234, "name": null},
+            {"value": 0x00ff, "name": "TLS_EMPTY_RENEGOTIATION_INFO_SCSV"},
+        ])
+    );
+    // IANA names, reserved code points included.
+    assert_eq!(
+        value["extensions"],
+        json!([
+            {"type": {"value": 0x7a7b, "name": null}, "data": "0102"},
+            {"type": {"value": 0x0028, "name": "Reserved"}, "data": ""},
+            {"type": {"value": 0xff01, "name": "renegotiation_info"}, "data": "00"},
+        ])
+    );
+    assert_eq!(value["server_name"], Value::Null);
+    assert_eq!(serde_json::from_value::<ClientHelloPayload>(value)?, chp);
+
+    // The view has the same field names.
+    let view = serde_json::to_value(chp.client_hello())?;
+    assert_eq!(
+        view["cipher_suites"],
+        serde_json::to_value(&chp)?["cipher_suites"]
+    );
+    assert!(view.get("random").is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid() -> Result<(), Box<dyn std::error::Error>> {
+    let chp = rustls_client_hello()?;
+    let doc = ClientHelloDocument::from(&chp);
+
+    let mut x = doc.clone();
+    x.schema_version = 2;
+    assert_eq!(
+        ClientHelloPayload::try_from(x),
+        Err(DocumentError::UnsupportedSchemaVersion(2))
+    );
+
+    let mut x = doc.clone();
+    x.random = "00".into();
+    assert_eq!(
+        ClientHelloPayload::try_from(x),
+        Err(DocumentError::InvalidRandom)
+    );
+
+    let mut x = doc.clone();
+    x.session_id = "zz".into();
+    assert_eq!(
+        ClientHelloPayload::try_from(x),
+        Err(DocumentError::InvalidHex)
+    );
+
+    let mut x = doc;
+    x.extensions[0].data.push_str("00");
+    assert!(matches!(
+        ClientHelloPayload::try_from(x),
+        Err(DocumentError::InvalidExtension(_))
+    ));
+
+    assert!(serde_json::from_str::<ClientHelloPayload>(r#"{"schema_version": 1}"#).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use tls_client_hello_parser::{
+    codec::Codec as _, enums::ServerNameType, server_name::normalize_host_name, ClientHelloPayload,
+    ServerNameError,
+};
+
+use common::minimal_client_hello_body;
+
+#[test]
+fn test_normalize_host_name() {
+    for (name, expected) in [
+        (&b"example.com"[..], Ok("example.com")),
+        (b"Example.COM", Ok("example.com")),
+        (b"example.com.", Ok("example.com")),
+        (b"localhost", Ok("localhost")),
+        (b"1.example.com", Ok("1.example.com")),
+        (b"xn--bcher-kva.example", Ok("xn--bcher-kva.example")),
+        (b"", Err(ServerNameError::Empty)),
+        (b".", Err(ServerNameError::Empty)),
+        (b"example.com..", Err(ServerNameError::EmptyLabel)),
+        (b"a..b", Err(ServerNameError::EmptyLabel)),
+        (b"192.168.1.1", Err(ServerNameError::IpAddress)),
+        (b"::1", Err(ServerNameError::IpAddress)),
+        (b"[2001:db8::1]", Err(ServerNameError::IpAddress)),
+        (b"1.2.3", Err(ServerNameError::NumericTopLevelLabel)),
+        (b"_dmarc.example.com", Ok("_dmarc.example.com")),
+        (b"a b.example", Err(ServerNameError::InvalidCharacter(b' '))),
+        (
+            "b\u{fc}cher.example".as_bytes(),
+            Err(ServerNameError::InvalidCharacter(0xc3)),
+        ),
+        (b"-a.example", Err(ServerNameError::HyphenAtLabelEdge)),
+        (b"a-.example", Err(ServerNameError::HyphenAtLabelEdge)),
+    ] {
+        assert_eq!(
+            normalize_host_name(name).as_deref().map_err(|x| *x),
+            expected,
+            "{:?}",
+            String::from_utf8_lossy(name)
+        );
+    }
+
+    let label = "a".repeat(64);
+    assert_eq!(
+        normalize_host_name(format!("{label}.com").as_bytes()),
+        Err(ServerNameError::LabelTooLong(64))
+    );
+    let name = vec!["a".repeat(63); 4].join(".");
+    assert_eq!(
+        normalize_host_name(name.as_bytes()),
+        Err(ServerNameError::TooLong(255))
+    );
+}
+
+#[test]
+fn test_server_names() -> Result<(), Box<dyn std::error::Error>> {
+    for (names, server_name, normalized) in [
+        (
+            &[(0, &b"Example.com."[..])][..],
+            Some("example.com."),
+            Ok(Some("example.com")),
+        ),
+        (
+            &[(0, b"example.com"), (0, b"example.org")],
+            Some("example.com"),
+            Err(ServerNameError::MultipleHostNames),
+        ),
+        (&[(0, b"10.0.0.1")], None, Err(ServerNameError::IpAddress)),
+        (
+            &[(0, b"My_Device.local")],
+            Some("my_device.local"),
+            Ok(Some("my_device.local")),
+        ),
+        (
+            &[(0, b"bad*.local")],
+            None,
+            Err(ServerNameError::InvalidCharacter(b'*')),
+        ),
+        (&[(1, b"\x01\x02")], None, Ok(None)),
+        (&[], None, Ok(None)),
+    ] {
+        let chp = ClientHelloPayload::read_bytes(&minimal_client_hello_body(&[(
+            0x0000,
+            common::server_name(names),
+        )]))?;
+        let client_hello = chp.client_hello();
+
+        assert_eq!(
+            client_hello.server_names().map(|x| x.collect::<Vec<_>>()),
+            Some(
+                names
+                    .iter()
+                    .map(|(typ, name)| (ServerNameType::from(*typ), *name))
+                    .collect()
+            )
+        );
+        assert_eq!(client_hello.server_name(), server_name);
+        assert_eq!(
+            client_hello
+                .normalized_server_name()
+                .as_ref()
+                .map(|x| x.as_deref()),
+            normalized.as_ref().map(|x| x.as_deref()),
+        );
+    }
+
+    Ok(())
+}